@@ -13,11 +13,13 @@
 //
 //   You should have received a copy of the GNU General Public License
 //   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use crate::ui::Ui;
 use embedded_graphics::Pixel;
 use embedded_graphics::pixelcolor::raw::RawU32;
 use embedded_graphics::prelude::{Dimensions, DrawTarget, PixelColor, Point, Size};
 use embedded_graphics::primitives::Rectangle;
 use sdl2::pixels::Color;
+use sdl2::rect::Rect;
 use sdl2::render::{BlendMode, Canvas, RenderTarget};
 
 pub struct CanvasDisplay<'a, T>
@@ -93,3 +95,54 @@ where
         Ok(())
     }
 }
+
+/// A draw target that draws each board cell as a scaled tile at its position in the window,
+/// for pixel-exact previews of what will be drawn into the world
+pub struct BoardPreview<'a, 'b, T>
+where
+    T: RenderTarget,
+{
+    pub display: CanvasDisplay<'a, T>,
+    pub ui: &'b Ui,
+}
+impl<'a, 'b, T> Dimensions for BoardPreview<'a, 'b, T>
+where
+    T: RenderTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        let (width, height) = self.ui.world_size();
+        Rectangle::new(Point::new(0, 0), Size::new(width as u32, height as u32))
+    }
+}
+impl<'a, 'b, T> DrawTarget for BoardPreview<'a, 'b, T>
+where
+    T: RenderTarget,
+{
+    type Color = Rgba8888Color;
+    type Error = String;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let scf = self.ui.scaling_factor as u32;
+        // Only draw inside the board area
+        let board = Rect::new(
+            self.display.left_padding,
+            self.display.top_padding,
+            (self.display.width as i32 - self.display.left_padding - self.display.right_padding)
+                as u32,
+            (self.display.height as i32 - self.display.top_padding - self.display.bottom_padding)
+                as u32,
+        );
+        self.display.canvas.set_blend_mode(BlendMode::Blend);
+        for pixel in pixels {
+            let (x, y) = self.ui.board_to_window_coordinate(pixel.0.x, pixel.0.y);
+            if let Some(tile) = Rect::new(x, y, scf, scf).intersection(board) {
+                self.display.canvas.set_draw_color::<Color>(pixel.1.into());
+                self.display.canvas.fill_rect(tile)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+//   sandbox - Lossless text encoding of elements
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Elements are encoded as their variant name, followed by all of their data separated by colons,
+//! e.g., `Sand`, `SaltWater:37` or `Ant:Digging(5):Left`.
+//! Encoded elements never contain whitespace or commas.
+
+use crate::element::{
+    Agent, AgentState, CombustionProduct, CombustionProducts, Concentration, Element, ElementKind,
+    Heading, Hydration, MAX_CONCENTRATION, MAX_HYDRATION,
+};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+/// The stable name of the given element variant, independent of its name in the UI
+const fn variant_name(element: &Element) -> &'static str {
+    match element {
+        Element::None => "None",
+        Element::BrickWall => "BrickWall",
+        Element::Wood => "Wood",
+        Element::Stone => "Stone",
+        Element::Glass => "Glass",
+        Element::Ice => "Ice",
+        Element::Plant { .. } => "Plant",
+        Element::Vine => "Vine",
+        Element::Fungus => "Fungus",
+        Element::Ant { .. } => "Ant",
+        Element::Fish { .. } => "Fish",
+        Element::Bird { .. } => "Bird",
+        Element::Sand => "Sand",
+        Element::Salt => "Salt",
+        Element::Lye => "Lye",
+        Element::Snow => "Snow",
+        Element::Seed => "Seed",
+        Element::Dust => "Dust",
+        Element::Ash => "Ash",
+        Element::Soot => "Soot",
+        Element::ColdLava => "ColdLava",
+        Element::WetDust => "WetDust",
+        Element::Water => "Water",
+        Element::Lava => "Lava",
+        Element::SaltWater { .. } => "SaltWater",
+        Element::Gasoline => "Gasoline",
+        Element::Honey => "Honey",
+        Element::MoltenGlass => "MoltenGlass",
+        Element::Acid => "Acid",
+        Element::WaterSource => "WaterSource",
+        Element::GasolineSource => "GasolineSource",
+        Element::FireSource => "FireSource",
+        Element::Volcano => "Volcano",
+        Element::Cryo => "Cryo",
+        Element::Steam => "Steam",
+        Element::Hydrogen => "Hydrogen",
+        Element::Methane => "Methane",
+        Element::Oxygen => "Oxygen",
+        Element::ToxicGas => "ToxicGas",
+        Element::Smoke => "Smoke",
+        Element::BlackSmoke => "BlackSmoke",
+        Element::HydrogenBurner => "HydrogenBurner",
+        Element::MethaneBurner => "MethaneBurner",
+        Element::Flame => "Flame",
+        Element::BurningParticle { .. } => "BurningParticle",
+        Element::Sink => "Sink",
+    }
+}
+
+fn encode_agent(agent: &Agent) -> String {
+    let state = match agent.state {
+        AgentState::Idle => "Idle".to_string(),
+        AgentState::Walking => "Walking".to_string(),
+        AgentState::Digging { ticks } => format!("Digging({})", ticks),
+        AgentState::Falling => "Falling".to_string(),
+        AgentState::Swimming => "Swimming".to_string(),
+        AgentState::Stranded { ticks } => format!("Stranded({})", ticks),
+        AgentState::Flying => "Flying".to_string(),
+        AgentState::Perching { ticks } => format!("Perching({})", ticks),
+    };
+    let heading = match agent.heading {
+        Heading::Left => "Left",
+        Heading::Right => "Right",
+    };
+    format!("{}:{}", state, heading)
+}
+
+fn encode_kind(kind: &ElementKind) -> String {
+    match kind {
+        ElementKind::None => "None".to_string(),
+        ElementKind::Solid => "Solid".to_string(),
+        ElementKind::Powder { density } => format!("Powder({})", density),
+        ElementKind::Liquid { density } => format!("Liquid({})", density),
+        ElementKind::Gas { density } => format!("Gas({})", density),
+    }
+}
+
+fn encode_product(product: &Option<CombustionProduct>) -> &'static str {
+    match product {
+        None => "-",
+        Some(CombustionProduct::Ash) => "Ash",
+        Some(CombustionProduct::Soot) => "Soot",
+        Some(CombustionProduct::Smoke) => "Smoke",
+        Some(CombustionProduct::BlackSmoke) => "BlackSmoke",
+        Some(CombustionProduct::Steam) => "Steam",
+    }
+}
+
+/// Encode the given element, including all of its data
+pub fn encode_element(element: &Element) -> String {
+    let name = variant_name(element);
+    match element {
+        Element::Plant { hydration } => format!("{}:{}", name, hydration.0),
+        Element::SaltWater { concentration } => format!("{}:{}", name, concentration.0),
+        Element::Ant { agent } | Element::Fish { agent } | Element::Bird { agent } => {
+            format!("{}:{}", name, encode_agent(agent))
+        },
+        Element::BurningParticle {
+            burned_element_kind,
+            decay_prob,
+            flame_spawn_prob,
+            products,
+        } => format!(
+            "{}:{}:{}:{}:{}:{}",
+            name,
+            encode_kind(burned_element_kind),
+            decay_prob,
+            flame_spawn_prob,
+            encode_product(&products.residue),
+            encode_product(&products.exhaust),
+        ),
+        _ => name.to_string(),
+    }
+}
+
+/// Split a token like `Digging(5)` into its name and its parameter
+fn split_parameter(token: &str) -> (&str, Option<&str>) {
+    match token.strip_suffix(')').and_then(|t| t.split_once('(')) {
+        Some((name, parameter)) => (name, Some(parameter)),
+        None => (token, None),
+    }
+}
+
+/// Parse a number that must lie within the given bounds
+fn parse_bounded<T: FromStr + PartialOrd>(token: &str, min: T, max: T) -> Result<T, String> {
+    token
+        .parse()
+        .ok()
+        .filter(|value| *value >= min && *value <= max)
+        .ok_or_else(|| format!("Invalid value '{}'", token))
+}
+
+fn decode_agent(state: &str, heading: &str) -> Result<Agent, String> {
+    let ticks = |parameter: Option<&str>| parse_bounded(parameter.unwrap_or_default(), 0, u8::MAX);
+    let state = match split_parameter(state) {
+        ("Idle", None) => AgentState::Idle,
+        ("Walking", None) => AgentState::Walking,
+        ("Digging", ticks_token @ Some(_)) => AgentState::Digging {
+            ticks: ticks(ticks_token)?,
+        },
+        ("Falling", None) => AgentState::Falling,
+        ("Swimming", None) => AgentState::Swimming,
+        ("Stranded", ticks_token @ Some(_)) => AgentState::Stranded {
+            ticks: ticks(ticks_token)?,
+        },
+        ("Flying", None) => AgentState::Flying,
+        ("Perching", ticks_token @ Some(_)) => AgentState::Perching {
+            ticks: ticks(ticks_token)?,
+        },
+        _ => return Err(format!("Unknown creature state '{}'", state)),
+    };
+    let heading = match heading {
+        "Left" => Heading::Left,
+        "Right" => Heading::Right,
+        _ => return Err(format!("Unknown heading '{}'", heading)),
+    };
+    Ok(Agent { state, heading })
+}
+
+fn decode_kind(token: &str) -> Result<ElementKind, String> {
+    let density = |parameter: Option<&str>| {
+        parse_bounded(parameter.unwrap_or_default(), f32::MIN_POSITIVE, f32::MAX)
+    };
+    match split_parameter(token) {
+        ("None", None) => Ok(ElementKind::None),
+        ("Solid", None) => Ok(ElementKind::Solid),
+        ("Powder", parameter @ Some(_)) => Ok(ElementKind::Powder {
+            density: density(parameter)?,
+        }),
+        ("Liquid", parameter @ Some(_)) => Ok(ElementKind::Liquid {
+            density: density(parameter)?,
+        }),
+        ("Gas", parameter @ Some(_)) => Ok(ElementKind::Gas {
+            density: density(parameter)?,
+        }),
+        _ => Err(format!("Unknown element kind '{}'", token)),
+    }
+}
+
+fn decode_product(token: &str) -> Result<Option<CombustionProduct>, String> {
+    match token {
+        "-" => Ok(None),
+        "Ash" => Ok(Some(CombustionProduct::Ash)),
+        "Soot" => Ok(Some(CombustionProduct::Soot)),
+        "Smoke" => Ok(Some(CombustionProduct::Smoke)),
+        "BlackSmoke" => Ok(Some(CombustionProduct::BlackSmoke)),
+        "Steam" => Ok(Some(CombustionProduct::Steam)),
+        _ => Err(format!("Unknown combustion product '{}'", token)),
+    }
+}
+
+/// Decode an element encoded by [encode_element].
+/// Only the exact encoding of a valid element is accepted, so every decoded element encodes
+/// back to the same text.
+pub fn decode_element(encoded: &str) -> Result<Element, String> {
+    let mut fields = encoded.split(':');
+    let name = fields.next().unwrap_or_default();
+    let fields: Vec<&str> = fields.collect();
+    let variant = Element::iter()
+        .find(|e| variant_name(e) == name)
+        .ok_or_else(|| format!("Unknown element '{}'", name))?;
+    let element = match (variant, &fields[..]) {
+        (Element::Plant { .. }, [hydration]) => Element::Plant {
+            hydration: Hydration(parse_bounded(hydration, 0, MAX_HYDRATION)?),
+        },
+        (Element::SaltWater { .. }, [concentration]) => Element::SaltWater {
+            concentration: Concentration(parse_bounded(concentration, 0, MAX_CONCENTRATION)?),
+        },
+        (Element::Ant { .. }, [state, heading]) => Element::Ant {
+            agent: decode_agent(state, heading)?,
+        },
+        (Element::Fish { .. }, [state, heading]) => Element::Fish {
+            agent: decode_agent(state, heading)?,
+        },
+        (Element::Bird { .. }, [state, heading]) => Element::Bird {
+            agent: decode_agent(state, heading)?,
+        },
+        (
+            Element::BurningParticle { .. },
+            [kind, decay_prob, flame_spawn_prob, residue, exhaust],
+        ) => Element::BurningParticle {
+            burned_element_kind: decode_kind(kind)?,
+            // The burning particle decays with a probability of 1/decay_prob
+            decay_prob: parse_bounded(decay_prob, 1, usize::MAX)?,
+            flame_spawn_prob: parse_bounded(flame_spawn_prob, 0.0, 1.0)?,
+            products: CombustionProducts {
+                residue: decode_product(residue)?,
+                exhaust: decode_product(exhaust)?,
+            },
+        },
+        (
+            Element::Plant { .. }
+            | Element::SaltWater { .. }
+            | Element::Ant { .. }
+            | Element::Fish { .. }
+            | Element::Bird { .. }
+            | Element::BurningParticle { .. },
+            _,
+        ) => return Err(format!("Invalid data of element '{}'", encoded)),
+        (element, []) => element,
+        (_, _) => return Err(format!("Element '{}' has no data", name)),
+    };
+    if encode_element(&element) != encoded {
+        return Err(format!("Invalid element '{}'", encoded));
+    }
+    Ok(element)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codec::{decode_element, encode_element};
+    use crate::element::{
+        Agent, AgentState, CombustionProduct, CombustionProducts, Concentration, Element,
+        ElementKind, Heading, Hydration,
+    };
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_elements_round_trip_with_their_data() {
+        let elements = Element::iter().chain([
+            Element::Plant {
+                hydration: Hydration(7),
+            },
+            Element::SaltWater {
+                concentration: Concentration(37),
+            },
+            Element::Bird {
+                agent: Agent {
+                    state: AgentState::Perching { ticks: 12 },
+                    heading: Heading::Right,
+                },
+            },
+            Element::BurningParticle {
+                burned_element_kind: ElementKind::Powder { density: 0.6 },
+                decay_prob: 50,
+                flame_spawn_prob: 0.35,
+                products: CombustionProducts {
+                    residue: Some(CombustionProduct::Ash),
+                    exhaust: None,
+                },
+            },
+        ]);
+        // The default burning particle without any decay probability is not a valid element
+        let elements =
+            elements.filter(|e| !matches!(e, Element::BurningParticle { decay_prob: 0, .. }));
+        for element in elements {
+            assert_eq!(decode_element(&encode_element(&element)), Ok(element));
+        }
+    }
+    #[test]
+    fn test_invalid_elements_are_rejected() {
+        for encoded in [
+            "INVALID",
+            "Saltwater",
+            "SaltWater",
+            "SaltWater:101",
+            "SaltWater:037",
+            "Sand:1",
+            "Ant:Digging:Left",
+            "BurningParticle",
+            "BurningParticle:Powder(0.6):0:0.35:Ash:-",
+            "BurningParticle:Powder(0.6):50:1.5:Ash:-",
+        ] {
+            assert!(decode_element(encoded).is_err(), "{}", encoded);
+        }
+    }
+}
@@ -14,12 +14,17 @@
 //   You should have received a copy of the GNU General Public License
 //   along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::element::Element;
+use crate::element::{Element, MAX_CONCENTRATION};
+use crate::world::GameWorld;
 use sdl2::pixels::Color;
 
 pub const BOARD_BORDER_COLOR: Color = Color::RGB(255, 255, 255);
 pub const WINDOW_BACKGROUND_COLOR: Color = Color::RGB(0, 0, 0);
 pub const BOARD_BACKGROUND_COLOR: Color = Color::RGB(20, 0, 60);
+pub const GRAVITY_ZONE_COLOR: Color = Color::RGBA(0x60, 0xd0, 0xff, 0x90);
+pub const ATTRACTOR_COLOR: Color = Color::RGBA(0xb0, 0x60, 0xff, 0x90);
+pub const REPULSOR_COLOR: Color = Color::RGBA(0xff, 0xd0, 0x60, 0x90);
+pub const SELECTION_COLOR: Color = Color::RGBA(0x60, 0xa0, 0xff, 0x50);
 impl Element {
     pub const fn color(&self) -> Color {
         match self {
@@ -27,8 +32,18 @@ impl Element {
             Element::Sand => Color::RGB(0xda, 0xca, 0xb3),
             Element::BrickWall => Color::RGB(0x8c, 0x3d, 0x20),
             Element::Wood => Color::RGB(0x8c, 0x5b, 0x30),
+            Element::Stone => Color::RGB(0x6e, 0x6a, 0x66),
             Element::Water => Color::RGB(0x05, 0xaf, 0xf2),
-            Element::SaltWater => Color::RGB(0x04, 0x9f, 0xc0),
+            // Salt water gets more grayish with increasing concentration
+            Element::SaltWater { concentration } => {
+                let c = concentration.0 as u16;
+                let max = MAX_CONCENTRATION as u16;
+                Color::RGB(
+                    (0x05 + (0x60 - 0x05) * c / max) as u8,
+                    (0xaf - (0xaf - 0x80) * c / max) as u8,
+                    (0xf2 - (0xf2 - 0x90) * c / max) as u8,
+                )
+            },
             Element::Salt => Color::RGB(0xd7, 0xd7, 0xd9),
             Element::WaterSource => Color::RGB(0x9c, 0xad, 0xbc),
             Element::Steam => Color::RGB(0xee, 0xee, 0xff),
@@ -44,10 +59,81 @@ impl Element {
             Element::FireSource => Color::RGB(0xd6, 0x9f, 0x7e),
             Element::Volcano => Color::RGB(0xc6, 0x8f, 0x6e),
             Element::Gasoline => Color::RGB(0x92, 0x19, 0x09),
+            Element::Honey => Color::RGB(0xe8, 0xa3, 0x17),
             Element::GasolineSource => Color::RGB(0x82, 0x15, 0x06),
             Element::Sink => Color::RGB(0x3, 0x0, 0x15),
             Element::Lava => Color::RGB(0xd2, 0x51, 0x16),
             Element::ColdLava => Color::RGB(0x8b, 0x8b, 0x8c),
+            Element::Acid => Color::RGB(0x9a, 0xe6, 0x1b),
+            Element::Lye => Color::RGB(0xf0, 0xf0, 0xe0),
+            Element::Glass => Color::RGB(0xc8, 0xe8, 0xf0),
+            Element::ToxicGas => Color::RGB(0x7a, 0x9a, 0x20),
+            Element::Ice => Color::RGB(0xa5, 0xe4, 0xf3),
+            Element::Snow => Color::RGB(0xf4, 0xf8, 0xff),
+            Element::Cryo => Color::RGB(0x6c, 0xc8, 0xff),
+            Element::MoltenGlass => Color::RGB(0xf2, 0x8c, 0x28),
+            Element::Seed => Color::RGB(0xa0, 0x7a, 0x3c),
+            Element::Plant { hydration } if hydration.0 == 0 => Color::RGB(0x8a, 0x6d, 0x2c),
+            Element::Plant { .. } => Color::RGB(0x3c, 0xb0, 0x3a),
+            Element::Vine => Color::RGB(0x2e, 0x8b, 0x3a),
+            Element::Fungus => Color::RGB(0xb8, 0xa8, 0x8c),
+            Element::Ant { .. } => Color::RGB(0x3a, 0x1c, 0x10),
+            Element::Fish { .. } => Color::RGB(0xff, 0x8c, 0x42),
+            Element::Bird { .. } => Color::RGB(0xe0, 0x40, 0x50),
+            Element::Soot => Color::RGB(0x1c, 0x18, 0x16),
+            Element::Smoke => Color::RGB(0x7c, 0x7c, 0x80),
+            Element::BlackSmoke => Color::RGB(0x2a, 0x28, 0x28),
+            Element::Oxygen => Color::RGB(0xb4, 0xdc, 0xff),
         }
     }
+    /// The opacity of an element when rendered, between 0 and 1.
+    /// Transparent elements are tinted with the color of the elements behind them.
+    pub const fn opacity(&self) -> f32 {
+        match self {
+            Element::Glass => 0.35,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Blend the given foreground color over the given background color
+fn blend(fg: Color, bg: Color, alpha: f32) -> Color {
+    let mix = |f: u8, b: u8| (f as f32 * alpha + b as f32 * (1.0 - alpha)) as u8;
+    Color::RGB(mix(fg.r, bg.r), mix(fg.g, bg.g), mix(fg.b, bg.b))
+}
+
+impl GameWorld {
+    /// The rendered color of the given cell.
+    /// Transparent elements show the average color of their opaque neighbors, tinted with their own color.
+    pub fn color_at(&self, x: usize, y: usize) -> Color {
+        let element = self.board()[x][y];
+        if element.opacity() >= 1.0 {
+            return element.color();
+        }
+        let (mut r, mut g, mut b, mut n) = (0u32, 0u32, 0u32, 0u32);
+        for (x_offs, y_offs) in [(0, -1), (-1, 0), (1, 0), (0, 1)] {
+            let n_x = x as i32 + x_offs;
+            let n_y = y as i32 + y_offs;
+            if n_x < 0
+                || n_y < 0
+                || n_x >= self.viewport_width() as i32
+                || n_y >= self.viewport_height() as i32
+            {
+                continue;
+            }
+            let neighbor = self.board()[n_x as usize][n_y as usize];
+            if neighbor.opacity() >= 1.0 {
+                let color = neighbor.color();
+                r += color.r as u32;
+                g += color.g as u32;
+                b += color.b as u32;
+                n += 1;
+            }
+        }
+        let behind = match n {
+            0 => BOARD_BACKGROUND_COLOR,
+            n => Color::RGB((r / n) as u8, (g / n) as u8, (b / n) as u8),
+        };
+        blend(element.color(), behind, element.opacity())
+    }
 }
@@ -18,30 +18,77 @@ use std::fmt::{Display, Formatter};
 use strum_macros::EnumIter;
 
 pub const AIR_DENSITY: f32 = 1.2754;
+/// The density of the heaviest element in kg/m³
+pub const MAX_DENSITY: f32 = 3100.0;
 /// The probability of a flame particle to decay
 pub const FLAME_DECAY_PROB: usize = 10;
+/// The probability of a toxic gas particle to dissipate in one tick
+pub const TOXIC_GAS_DECAY_PROB: f64 = 0.002;
+/// The probability of a smoke particle to dissipate in one tick
+pub const SMOKE_DECAY_PROB: f64 = 0.01;
+/// The probability of a black smoke particle to dissipate in one tick
+pub const BLACK_SMOKE_DECAY_PROB: f64 = 0.004;
+/// The temperature in °C of all elements without a temperature of their own
+pub const ROOM_TEMPERATURE: f32 = 20.0;
+/// The salt concentration of a saturated solution that cannot dissolve any more salt
+pub const MAX_CONCENTRATION: u8 = 100;
+/// The salt concentration of sea water
+pub const SEAWATER_CONCENTRATION: u8 = 20;
+/// The maximum amount of water a plant cell can store
+pub const MAX_HYDRATION: u8 = 30;
 #[derive(Copy, Clone, PartialEq, Debug, EnumIter)]
 pub enum Element {
     None,
     BrickWall,
     Wood,
+    Stone,
+    Glass,
+    Ice,
+    Plant {
+        hydration: Hydration,
+    },
+    Vine,
+    Fungus,
+    Ant {
+        agent: Agent,
+    },
+    Fish {
+        agent: Agent,
+    },
+    Bird {
+        agent: Agent,
+    },
     Sand,
     Salt,
+    Lye,
+    Snow,
+    Seed,
     Dust,
     Ash,
+    Soot,
     ColdLava,
     WetDust,
     Water,
     Lava,
-    SaltWater,
+    SaltWater {
+        concentration: Concentration,
+    },
     Gasoline,
+    Honey,
+    MoltenGlass,
+    Acid,
     WaterSource,
     GasolineSource,
     FireSource,
     Volcano,
+    Cryo,
     Steam,
     Hydrogen,
     Methane,
+    Oxygen,
+    ToxicGas,
+    Smoke,
+    BlackSmoke,
     HydrogenBurner,
     MethaneBurner,
     Flame,
@@ -50,11 +97,80 @@ pub enum Element {
         burned_element_kind: ElementKind,
         decay_prob: usize,
         flame_spawn_prob: f64,
-        spawns_ash: bool,
+        products: CombustionProducts,
     },
     Sink,
 }
 
+/// The amount of water stored in a plant cell, between 0 and [MAX_HYDRATION].
+/// Newly placed plants are fully hydrated.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hydration(pub u8);
+impl Default for Hydration {
+    fn default() -> Self {
+        Hydration(MAX_HYDRATION)
+    }
+}
+
+/// The salt concentration of salt water, between 0 and [MAX_CONCENTRATION] (saturated).
+/// Newly placed salt water has the concentration of sea water.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Concentration(pub u8);
+impl Default for Concentration {
+    fn default() -> Self {
+        Concentration(SEAWATER_CONCENTRATION)
+    }
+}
+/// The direction a creature is facing
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum Heading {
+    #[default]
+    Left,
+    Right,
+}
+impl Heading {
+    /// The horizontal offset of one step into this direction
+    pub const fn offset(&self) -> i32 {
+        match self {
+            Heading::Left => -1,
+            Heading::Right => 1,
+        }
+    }
+    pub const fn reversed(&self) -> Heading {
+        match self {
+            Heading::Left => Heading::Right,
+            Heading::Right => Heading::Left,
+        }
+    }
+}
+/// The state of the state machine of a creature
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum AgentState {
+    /// Newly placed creatures choose their state depending on their surroundings
+    #[default]
+    Idle,
+    /// An ant walking on a surface
+    Walking,
+    /// An ant digging a tunnel through sand for the given number of ticks
+    Digging { ticks: u8 },
+    /// A creature falling down without anything to hold on to
+    Falling,
+    /// A fish swimming in water
+    Swimming,
+    /// A fish that has been out of water for the given number of ticks
+    Stranded { ticks: u8 },
+    /// A bird flying through the air
+    Flying,
+    /// A bird resting on a surface for the given number of ticks
+    Perching { ticks: u8 },
+}
+/// The state of a living creature
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Agent {
+    pub state: AgentState,
+    pub heading: Heading,
+}
+
 #[derive(Copy, Clone, PartialEq, Debug, Default, EnumIter)]
 pub enum ElementKind {
     #[default]
@@ -78,6 +194,34 @@ pub enum ElementKind {
         density: f32,
     },
 }
+/// A byproduct of burning elements
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CombustionProduct {
+    Ash,
+    Soot,
+    Smoke,
+    BlackSmoke,
+    Steam,
+}
+impl CombustionProduct {
+    pub const fn element(&self) -> Element {
+        match self {
+            CombustionProduct::Ash => Element::Ash,
+            CombustionProduct::Soot => Element::Soot,
+            CombustionProduct::Smoke => Element::Smoke,
+            CombustionProduct::BlackSmoke => Element::BlackSmoke,
+            CombustionProduct::Steam => Element::Steam,
+        }
+    }
+}
+/// The byproducts of a burning element
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct CombustionProducts {
+    /// The element a burning particle leaves behind when it burns down
+    pub residue: Option<CombustionProduct>,
+    /// The gas a burning particle emits instead of some of its flames
+    pub exhaust: Option<CombustionProduct>,
+}
 #[derive(Copy, Clone, PartialEq, Debug, Default)]
 pub enum Flammability {
     #[default]
@@ -89,6 +233,8 @@ pub enum Flammability {
         decay_prob: usize,
         /// The probability of this burning element to spawn a flame when burning
         flame_spawn_prob: f64,
+        /// The byproducts of this element when burning
+        products: CombustionProducts,
     },
 }
 
@@ -127,16 +273,19 @@ impl Element {
         match self {
             Element::Flame => Some(1.0 / FLAME_DECAY_PROB as f64),
             Element::BurningParticle { decay_prob, .. } => Some(1.0 / *decay_prob as f64),
+            Element::ToxicGas => Some(TOXIC_GAS_DECAY_PROB),
+            Element::Smoke => Some(SMOKE_DECAY_PROB),
+            Element::BlackSmoke => Some(BLACK_SMOKE_DECAY_PROB),
             _ => None,
         }
     }
 
     pub const fn decays_to(&self) -> Option<Element> {
         match self {
-            // A burning Particle decays to whatever it contains as decays_to
-            Element::BurningParticle { spawns_ash, .. } => match spawns_ash {
-                false => None,
-                true => Some(Element::Ash),
+            // A burning Particle decays to the residue of the burned element
+            Element::BurningParticle { products, .. } => match products.residue {
+                None => None,
+                Some(residue) => Some(residue.element()),
             },
             _ => None,
         }
@@ -146,6 +295,9 @@ impl Element {
             Element::None => false,
             Element::ColdLava => false,
             Element::Ash => false,
+            Element::Soot => false,
+            Element::Smoke => false,
+            Element::BlackSmoke => false,
             Element::BurningParticle { .. } => false,
             _ => true,
         }
@@ -164,7 +316,9 @@ impl Element {
             Element::Sand => ElementKind::Powder { density: 1700.0 },
             Element::BrickWall => ElementKind::Solid,
             Element::Water => ElementKind::Liquid { density: 997.0 },
-            Element::SaltWater => ElementKind::Liquid { density: 1027.0 },
+            Element::SaltWater { concentration } => ElementKind::Liquid {
+                density: 997.0 + concentration.0 as f32 * 1.5,
+            },
             Element::Lava => ElementKind::Liquid { density: 3100.0 },
             Element::Salt => ElementKind::Powder { density: 2170.0 },
             Element::WaterSource => ElementKind::Solid,
@@ -182,12 +336,33 @@ impl Element {
             Element::FireSource => ElementKind::Solid,
             Element::Volcano => ElementKind::Solid,
             Element::Gasoline => ElementKind::Liquid { density: 737.0 },
+            Element::Honey => ElementKind::Liquid { density: 1420.0 },
             Element::HydrogenBurner => ElementKind::Solid,
             Element::MethaneBurner => ElementKind::Solid,
             Element::Wood => ElementKind::Solid,
+            Element::Stone => ElementKind::Solid,
             Element::Ash => ElementKind::Powder { density: 1.5 },
             Element::ColdLava => ElementKind::Powder { density: 3050.0 },
             Element::Sink => ElementKind::Solid,
+            Element::Acid => ElementKind::Liquid { density: 1100.0 },
+            Element::Lye => ElementKind::Powder { density: 2130.0 },
+            Element::Glass => ElementKind::Solid,
+            Element::ToxicGas => ElementKind::Gas { density: 0.9 },
+            Element::Ice => ElementKind::Solid,
+            Element::Snow => ElementKind::Powder { density: 200.0 },
+            Element::Cryo => ElementKind::Solid,
+            Element::MoltenGlass => ElementKind::Liquid { density: 2400.0 },
+            Element::Seed => ElementKind::Powder { density: 700.0 },
+            Element::Plant { .. } => ElementKind::Solid,
+            Element::Vine => ElementKind::Solid,
+            Element::Fungus => ElementKind::Solid,
+            Element::Ant { .. } => ElementKind::Solid,
+            Element::Fish { .. } => ElementKind::Solid,
+            Element::Bird { .. } => ElementKind::Solid,
+            Element::Soot => ElementKind::Powder { density: 40.0 },
+            Element::Smoke => ElementKind::Gas { density: 0.9 },
+            Element::BlackSmoke => ElementKind::Gas { density: 1.0 },
+            Element::Oxygen => ElementKind::Gas { density: 1.43 },
         }
     }
     /// The flammability properties of flammable elements
@@ -201,10 +376,14 @@ impl Element {
                 prob: 0.75,
                 decay_prob: 25,
                 flame_spawn_prob: 0.05,
+                products: CombustionProducts {
+                    residue: None,
+                    exhaust: Some(CombustionProduct::Smoke),
+                },
             },
             Element::WetDust => Flammability::NotFlammable,
             Element::Water => Flammability::NotFlammable,
-            Element::SaltWater => Flammability::NotFlammable,
+            Element::SaltWater { .. } => Flammability::NotFlammable,
             Element::WaterSource => Flammability::NotFlammable,
             Element::GasolineSource => Flammability::NotFlammable,
             Element::FireSource => Flammability::NotFlammable,
@@ -214,11 +393,19 @@ impl Element {
                 prob: 0.98,
                 decay_prob: 2,
                 flame_spawn_prob: 0.95,
+                products: CombustionProducts {
+                    residue: None,
+                    exhaust: Some(CombustionProduct::Steam),
+                },
             },
             Element::Methane => Flammability::Flammable {
                 prob: 0.12,
                 decay_prob: 8,
                 flame_spawn_prob: 0.62,
+                products: CombustionProducts {
+                    residue: None,
+                    exhaust: Some(CombustionProduct::Steam),
+                },
             },
             Element::Flame => Flammability::NotFlammable,
             Element::BurningParticle { .. } => Flammability::NotFlammable,
@@ -226,6 +413,10 @@ impl Element {
                 prob: 0.45,
                 decay_prob: 65,
                 flame_spawn_prob: 0.12,
+                products: CombustionProducts {
+                    residue: Some(CombustionProduct::Soot),
+                    exhaust: Some(CombustionProduct::BlackSmoke),
+                },
             },
             Element::HydrogenBurner => Flammability::NotFlammable,
             Element::MethaneBurner => Flammability::NotFlammable,
@@ -233,11 +424,81 @@ impl Element {
                 prob: 0.0025,
                 decay_prob: 1000,
                 flame_spawn_prob: 0.05,
+                products: CombustionProducts {
+                    residue: Some(CombustionProduct::Ash),
+                    exhaust: Some(CombustionProduct::Smoke),
+                },
             },
+            Element::Stone => Flammability::NotFlammable,
             Element::Ash => Flammability::NotFlammable,
             Element::Sink => Flammability::NotFlammable,
             Element::ColdLava => Flammability::NotFlammable,
             Element::Lava => Flammability::NotFlammable,
+            Element::Honey => Flammability::NotFlammable,
+            Element::Acid => Flammability::NotFlammable,
+            Element::Lye => Flammability::NotFlammable,
+            Element::Glass => Flammability::NotFlammable,
+            Element::ToxicGas => Flammability::NotFlammable,
+            Element::Ice => Flammability::NotFlammable,
+            Element::Snow => Flammability::NotFlammable,
+            Element::Cryo => Flammability::NotFlammable,
+            Element::MoltenGlass => Flammability::NotFlammable,
+            Element::Seed => Flammability::Flammable {
+                prob: 0.05,
+                decay_prob: 100,
+                flame_spawn_prob: 0.05,
+                products: CombustionProducts {
+                    residue: None,
+                    exhaust: Some(CombustionProduct::Smoke),
+                },
+            },
+            // Wilted plants burn a lot easier
+            Element::Plant { hydration } => Flammability::Flammable {
+                prob: if hydration.0 == 0 { 0.05 } else { 0.0025 },
+                decay_prob: 1000,
+                flame_spawn_prob: 0.05,
+                products: CombustionProducts {
+                    residue: Some(CombustionProduct::Ash),
+                    exhaust: Some(CombustionProduct::Smoke),
+                },
+            },
+            Element::Vine => Flammability::Flammable {
+                prob: 0.0025,
+                decay_prob: 1000,
+                flame_spawn_prob: 0.05,
+                products: CombustionProducts {
+                    residue: Some(CombustionProduct::Ash),
+                    exhaust: Some(CombustionProduct::Smoke),
+                },
+            },
+            Element::Fungus => Flammability::Flammable {
+                prob: 0.0025,
+                decay_prob: 1000,
+                flame_spawn_prob: 0.05,
+                products: CombustionProducts {
+                    residue: Some(CombustionProduct::Ash),
+                    exhaust: Some(CombustionProduct::Smoke),
+                },
+            },
+            Element::Ant { .. } => Flammability::NotFlammable,
+            Element::Fish { .. } => Flammability::NotFlammable,
+            Element::Bird { .. } => Flammability::NotFlammable,
+            Element::Soot => Flammability::NotFlammable,
+            Element::Smoke => Flammability::NotFlammable,
+            Element::BlackSmoke => Flammability::NotFlammable,
+            // Oxygen does not burn itself, but feeds the fires around it
+            Element::Oxygen => Flammability::NotFlammable,
+        }
+    }
+    /// How strongly this element reacts to gravity fields, between 0 and 1.
+    /// Scales logarithmically with the density, so that even light gases are slightly affected.
+    /// Elements lighter than air are driven by their buoyancy instead, i.e., the lighter they are
+    /// compared to air, the stronger they rise against the field.
+    pub fn field_response(&self) -> f64 {
+        match self.density() {
+            None => 0.0,
+            Some(density) if density < AIR_DENSITY => (1.0 - density / AIR_DENSITY) as f64,
+            Some(density) => (density.ln_1p() / MAX_DENSITY.ln_1p()).min(1.0) as f64,
         }
     }
     pub const fn density(&self) -> Option<f32> {
@@ -249,6 +510,185 @@ impl Element {
             ElementKind::Gas { density } => Some(density),
         }
     }
+    /// The viscosity of liquids, between 0 and 1.
+    /// This is the probability of a liquid to skip spreading to the side in one tick.
+    pub const fn viscosity(&self) -> f64 {
+        match self {
+            Element::Water => 0.1,
+            Element::SaltWater { .. } => 0.12,
+            Element::Gasoline => 0.0,
+            Element::Lava => 0.85,
+            Element::Honey => 0.95,
+            Element::MoltenGlass => 0.9,
+            Element::Acid => 0.1,
+            _ => 0.0,
+        }
+    }
+    /// The surface tension of liquids, between 0 and 1.
+    /// This is the probability of a liquid to refuse spreading to the side if it would
+    /// detach from all other cells of the same liquid, i.e., small droplets cohere.
+    pub const fn surface_tension(&self) -> f64 {
+        match self {
+            Element::Water => 0.6,
+            Element::SaltWater { .. } => 0.6,
+            Element::Gasoline => 0.1,
+            Element::Lava => 0.8,
+            Element::Honey => 0.9,
+            Element::MoltenGlass => 0.8,
+            Element::Acid => 0.5,
+            _ => 0.0,
+        }
+    }
+    /// The maximum number of cells a liquid moves to the side in one tick
+    pub const fn dispersion(&self) -> usize {
+        match self {
+            Element::Water => 5,
+            Element::SaltWater { .. } => 4,
+            Element::Gasoline => 8,
+            Element::Lava => 1,
+            Element::Honey => 1,
+            Element::Acid => 4,
+            _ => 1,
+        }
+    }
+    /// The temperature in °C of elements that heat up or cool down adjacent elements.
+    /// All other elements are at room temperature.
+    pub const fn temperature(&self) -> Option<f32> {
+        match self {
+            Element::Cryo => Some(-40.0),
+            Element::Ice => Some(-5.0),
+            Element::Snow => Some(-5.0),
+            Element::BurningParticle { .. } => Some(600.0),
+            Element::Flame => Some(800.0),
+            Element::FireSource => Some(900.0),
+            Element::MoltenGlass => Some(1000.0),
+            Element::Lava => Some(1100.0),
+            Element::Volcano => Some(1200.0),
+            _ => None,
+        }
+    }
+    /// The temperature in °C below which a liquid freezes into ice
+    pub const fn freezing_point(&self) -> Option<f32> {
+        match self {
+            Element::Water => Some(0.0),
+            Element::SaltWater { concentration } => Some(-(concentration.0 as f32) / 2.0),
+            _ => None,
+        }
+    }
+    /// The resistance of this element against acid, between 0 and 1.
+    /// Elements with a resistance of 1 are never dissolved.
+    pub const fn corrosion_resistance(&self) -> f64 {
+        match self {
+            Element::None => 1.0,
+            Element::BrickWall => 0.97,
+            Element::Wood => 0.5,
+            Element::Stone => 0.9,
+            Element::Glass => 1.0,
+            Element::Sand => 0.8,
+            Element::Salt => 0.6,
+            Element::Lye => 1.0,
+            Element::Dust => 0.3,
+            Element::Ash => 1.0,
+            Element::ColdLava => 0.85,
+            Element::WetDust => 0.4,
+            Element::Water => 1.0,
+            Element::Lava => 1.0,
+            Element::SaltWater { .. } => 1.0,
+            Element::Gasoline => 0.9,
+            Element::Honey => 0.7,
+            Element::Acid => 1.0,
+            Element::WaterSource => 1.0,
+            Element::GasolineSource => 1.0,
+            Element::FireSource => 1.0,
+            Element::Volcano => 1.0,
+            Element::Steam => 1.0,
+            Element::Hydrogen => 1.0,
+            Element::Methane => 1.0,
+            Element::ToxicGas => 1.0,
+            Element::HydrogenBurner => 1.0,
+            Element::MethaneBurner => 1.0,
+            Element::Flame => 1.0,
+            Element::BurningParticle { .. } => 0.5,
+            Element::Ice => 1.0,
+            Element::Snow => 1.0,
+            Element::Cryo => 1.0,
+            Element::MoltenGlass => 1.0,
+            Element::Seed => 0.3,
+            Element::Plant { .. } => 0.3,
+            Element::Vine => 0.3,
+            Element::Fungus => 0.2,
+            Element::Ant { .. } => 0.2,
+            Element::Fish { .. } => 0.2,
+            Element::Bird { .. } => 0.2,
+            Element::Soot => 0.5,
+            Element::Smoke => 1.0,
+            Element::BlackSmoke => 1.0,
+            Element::Oxygen => 1.0,
+            Element::Sink => 1.0,
+        }
+    }
+    /// The density in kg/m³ of solids that can form rigid bodies.
+    /// Controls whether rigid bodies float or sink in liquids.
+    pub const fn rigid_density(&self) -> Option<f32> {
+        match self {
+            Element::Wood => Some(850.0),
+            Element::Stone => Some(2600.0),
+            _ => None,
+        }
+    }
+    /// The impact speed in cells per tick at which a rigid body made of this element breaks apart
+    pub const fn impact_strength(&self) -> Option<f32> {
+        match self {
+            Element::Wood => Some(4.5),
+            Element::Stone => Some(5.5),
+            _ => None,
+        }
+    }
+    /// The particles a rigid body made of this element breaks apart into
+    pub const fn debris(&self) -> Option<Element> {
+        match self {
+            Element::Wood => Some(Element::Dust),
+            Element::Stone => Some(Element::Sand),
+            _ => None,
+        }
+    }
+    /// Whether this element slowly mixes with the given adjacent element by diffusion
+    pub const fn miscible_with(&self, other: &Element) -> bool {
+        match (self, other) {
+            // Flames never mix with other gases
+            (Element::Flame | Element::BurningParticle { .. }, _) => false,
+            (_, Element::Flame | Element::BurningParticle { .. }) => false,
+            // Water-based liquids mix with each other, but not with oily liquids like gasoline
+            (
+                Element::Water | Element::SaltWater { .. } | Element::Acid | Element::Honey,
+                Element::Water | Element::SaltWater { .. } | Element::Acid | Element::Honey,
+            ) => true,
+            // Molten minerals mix with each other
+            (Element::Lava | Element::MoltenGlass, Element::Lava | Element::MoltenGlass) => true,
+            // All other gases mix freely
+            _ => {
+                matches!(self.kind(), ElementKind::Gas { .. })
+                    && matches!(other.kind(), ElementKind::Gas { .. })
+            },
+        }
+    }
+    /// The salt concentration of water and salt water
+    pub const fn salinity(&self) -> Option<u8> {
+        match self {
+            Element::Water => Some(0),
+            Element::SaltWater { concentration } => Some(concentration.0),
+            _ => None,
+        }
+    }
+    /// Water with the given salt concentration
+    pub const fn with_salinity(concentration: u8) -> Element {
+        match concentration {
+            0 => Element::Water,
+            c => Element::SaltWater {
+                concentration: Concentration(c),
+            },
+        }
+    }
     /// Whether the given element is a liquid or a gas, i.e., whether the element can swap its position with other elements
     pub const fn is_liquid_or_gas(&self) -> bool {
         match self.kind() {
@@ -276,14 +716,20 @@ impl Element {
                 "A solid brick wall. Inert to all other elements, acts as a solid."
             },
             Element::Wood => "A wooden wall. Blocks most elements, but can be lit on fire.",
-            Element::Sand => "Inert to all elements and moderately heavy.",
+            Element::Stone => "A heavy rock. Can be turned into falling boulders.",
+            Element::Sand => "Inert to most elements and moderately heavy.",
             Element::Salt => "Can be dissolved to salt water.",
-            Element::Dust => "A very light and flammable powder that can be soaked in water.",
-            Element::Ash => "An extremely light powder that is created by burning wood.",
+            Element::Dust => {
+                "A very light and flammable powder that floats on water and can be soaked in it."
+            },
+            Element::Ash => {
+                "An extremely light powder that is created by burning wood. Floats on liquids."
+            },
             Element::WetDust => "Dust that has been soaked in water. Leaks water over time.",
             Element::Water => "The element of life.",
-            Element::SaltWater => "A solution of water and salt.",
+            Element::SaltWater { .. } => "A solution of water and salt.",
             Element::Gasoline => "A highly flammable fuel.",
+            Element::Honey => "A sweet and very viscous liquid.",
             Element::WaterSource => "A source of water.",
             Element::GasolineSource => "A source of gasoline.",
             Element::FireSource => "A burning solid that never extinguishes.",
@@ -299,7 +745,40 @@ impl Element {
             Element::BurningParticle { .. } => "A burning particle.",
             Element::Sink => "A sink that destroys all adjacent elements.",
             Element::ColdLava => "A very heavy mixture of stone and minerals.",
-            Element::Lava => "Molten stone and volcanic minerals.",
+            Element::Lava => "Molten stone and volcanic minerals. Creeps slowly.",
+            Element::Acid => "A corrosive liquid that dissolves most elements.",
+            Element::Lye => "A caustic base that neutralizes acid to salt water.",
+            Element::Glass => "A transparent solid that resists acid and does not conduct heat.",
+            Element::ToxicGas => {
+                "A toxic gas that is released when acid dissolves elements. Dissipates slowly."
+            },
+            Element::Ice => "Frozen water. Melts near hot elements and freezes adjacent water.",
+            Element::Snow => {
+                "Light frozen powder. Melts near hot elements and compacts into ice under load."
+            },
+            Element::Cryo => "An extremely cold solid that freezes water and salt water.",
+            Element::MoltenGlass => {
+                "Sand that has been molten by extreme heat. Cools down into glass."
+            },
+            Element::Seed => {
+                "A seed that sprouts into a plant when resting on sand or wet dust next to water."
+            },
+            Element::Plant { .. } => {
+                "A plant that grows upward and branches while it has water. Wilts without water."
+            },
+            Element::Vine => "A vine that grows downward from where it is attached.",
+            Element::Fungus => "A fungus that slowly spreads along wood.",
+            Element::Ant { .. } => "An ant that walks on surfaces and digs through sand.",
+            Element::Fish { .. } => {
+                "A fish that swims in water. Dies in salt water and out of water."
+            },
+            Element::Bird { .. } => "A bird that flies through the air and perches on surfaces.",
+            Element::Soot => "A fine black powder that is left behind by burning fuel.",
+            Element::Smoke => "Smoke from burning wood and dust. Dissipates slowly.",
+            Element::BlackSmoke => "Thick black smoke from burning fuel. Dissipates very slowly.",
+            Element::Oxygen => {
+                "Pure oxygen. Makes fires flare up and lets smothered fires burn again."
+            },
         }
     }
 }
@@ -314,7 +793,7 @@ impl Display for Element {
                 Element::Sand => "Sand",
                 Element::BrickWall => "Wall",
                 Element::Water => "Water",
-                Element::SaltWater => "Saltwater",
+                Element::SaltWater { .. } => "Saltwater",
                 Element::Salt => "Salt",
                 Element::WaterSource => "Water Source",
                 Element::Steam => "Steam",
@@ -326,15 +805,36 @@ impl Display for Element {
                 Element::FireSource => "Fire Source",
                 Element::Volcano => "Volcano",
                 Element::Gasoline => "Gasoline",
+                Element::Honey => "Honey",
                 Element::GasolineSource => "Gas Station",
                 Element::Methane => "Methane",
                 Element::HydrogenBurner => "Hydrogen Burner",
                 Element::MethaneBurner => "Methane Burner",
                 Element::Wood => "Wood",
+                Element::Stone => "Stone",
                 Element::Ash => "Ash",
                 Element::Sink => "Sink",
                 Element::ColdLava => "Cold Lava",
                 Element::Lava => "Lava",
+                Element::Acid => "Acid",
+                Element::Lye => "Lye",
+                Element::Glass => "Glass",
+                Element::ToxicGas => "Toxic Gas",
+                Element::Ice => "Ice",
+                Element::Snow => "Snow",
+                Element::Cryo => "Cryo",
+                Element::MoltenGlass => "Molten Glass",
+                Element::Seed => "Seed",
+                Element::Plant { .. } => "Plant",
+                Element::Vine => "Vine",
+                Element::Fungus => "Fungus",
+                Element::Ant { .. } => "Ant",
+                Element::Fish { .. } => "Fish",
+                Element::Bird { .. } => "Bird",
+                Element::Soot => "Soot",
+                Element::Smoke => "Smoke",
+                Element::BlackSmoke => "Black Smoke",
+                Element::Oxygen => "Oxygen",
             }
         )
     }
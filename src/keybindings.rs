@@ -0,0 +1,396 @@
+//   sandbox - Keyboard shortcuts
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::config::config_dir;
+use sdl2::keyboard::{Keycode, Mod};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The number of elements that can be selected with a shortcut
+pub const ELEMENT_SHORTCUTS: usize = 10;
+
+/// An action that can be triggered with a keyboard shortcut
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Action {
+    Quit,
+    Help,
+    Pause,
+    Step,
+    Reset,
+    Undo,
+    Save,
+    Load,
+    ZoomIn,
+    ZoomOut,
+    NextCursor,
+    PreviousCursor,
+    GrowBrush,
+    ShrinkBrush,
+    SearchElement,
+    /// Select the element at the given index in the element panel
+    Element(usize),
+}
+
+impl Action {
+    /// All actions, in the order they are listed in the help overlay and the keybinding file
+    pub fn all() -> impl Iterator<Item = Action> {
+        [
+            Action::Quit,
+            Action::Help,
+            Action::Pause,
+            Action::Step,
+            Action::Reset,
+            Action::Undo,
+            Action::Save,
+            Action::Load,
+            Action::ZoomIn,
+            Action::ZoomOut,
+            Action::NextCursor,
+            Action::PreviousCursor,
+            Action::GrowBrush,
+            Action::ShrinkBrush,
+            Action::SearchElement,
+        ]
+        .into_iter()
+        .chain((0..ELEMENT_SHORTCUTS).map(Action::Element))
+    }
+    /// The name of this action in the keybinding file
+    pub fn name(&self) -> String {
+        match self {
+            Action::Quit => "quit".to_string(),
+            Action::Help => "help".to_string(),
+            Action::Pause => "pause".to_string(),
+            Action::Step => "step".to_string(),
+            Action::Reset => "reset".to_string(),
+            Action::Undo => "undo".to_string(),
+            Action::Save => "save".to_string(),
+            Action::Load => "load".to_string(),
+            Action::ZoomIn => "zoom_in".to_string(),
+            Action::ZoomOut => "zoom_out".to_string(),
+            Action::NextCursor => "next_cursor".to_string(),
+            Action::PreviousCursor => "previous_cursor".to_string(),
+            Action::GrowBrush => "grow_brush".to_string(),
+            Action::ShrinkBrush => "shrink_brush".to_string(),
+            Action::SearchElement => "search_element".to_string(),
+            Action::Element(index) => format!("element_{}", index + 1),
+        }
+    }
+    /// The text to show in the help overlay for this action
+    pub fn description(&self) -> String {
+        match self {
+            Action::Quit => "Quit".to_string(),
+            Action::Help => "Show or hide the keyboard shortcuts".to_string(),
+            Action::Pause => "Pause or resume the simulation".to_string(),
+            Action::Step => "Simulate a single tick while paused".to_string(),
+            Action::Reset => "Delete everything".to_string(),
+            Action::Undo => "Undo the last change".to_string(),
+            Action::Save => "Save the world".to_string(),
+            Action::Load => "Load the saved world".to_string(),
+            Action::ZoomIn => "Zoom in".to_string(),
+            Action::ZoomOut => "Zoom out".to_string(),
+            Action::NextCursor => "Select the next cursor".to_string(),
+            Action::PreviousCursor => "Select the previous cursor".to_string(),
+            Action::GrowBrush => "Grow the brush".to_string(),
+            Action::ShrinkBrush => "Shrink the brush".to_string(),
+            Action::SearchElement => "Search for an element".to_string(),
+            Action::Element(index) => format!("Select element {} of the element panel", index + 1),
+        }
+    }
+    /// The key that triggers this action if the keybinding file does not bind it
+    const fn default_binding(&self) -> KeyBinding {
+        match self {
+            Action::Quit => KeyBinding::new(Keycode::Escape),
+            Action::Help => KeyBinding::new(Keycode::F1),
+            Action::Pause => KeyBinding::new(Keycode::Space),
+            Action::Step => KeyBinding::new(Keycode::Period),
+            Action::Reset => KeyBinding::ctrl(Keycode::R),
+            Action::Undo => KeyBinding::ctrl(Keycode::Z),
+            Action::Save => KeyBinding::ctrl(Keycode::S),
+            Action::Load => KeyBinding::ctrl(Keycode::O),
+            Action::ZoomIn => KeyBinding::new(Keycode::Equals),
+            Action::ZoomOut => KeyBinding::new(Keycode::Minus),
+            Action::NextCursor => KeyBinding::new(Keycode::Tab),
+            Action::PreviousCursor => KeyBinding {
+                key: Keycode::Tab,
+                ctrl: false,
+                shift: true,
+            },
+            Action::GrowBrush => KeyBinding::new(Keycode::RightBracket),
+            Action::ShrinkBrush => KeyBinding::new(Keycode::LeftBracket),
+            Action::SearchElement => KeyBinding::ctrl(Keycode::F),
+            Action::Element(index) => KeyBinding::new(match index {
+                0 => Keycode::Num1,
+                1 => Keycode::Num2,
+                2 => Keycode::Num3,
+                3 => Keycode::Num4,
+                4 => Keycode::Num5,
+                5 => Keycode::Num6,
+                6 => Keycode::Num7,
+                7 => Keycode::Num8,
+                8 => Keycode::Num9,
+                _ => Keycode::Num0,
+            }),
+        }
+    }
+}
+
+/// A key together with the modifier keys that must be held down
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct KeyBinding {
+    pub key: Keycode,
+    pub ctrl: bool,
+    pub shift: bool,
+}
+
+impl KeyBinding {
+    const fn new(key: Keycode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+        }
+    }
+    const fn ctrl(key: Keycode) -> Self {
+        Self {
+            key,
+            ctrl: true,
+            shift: false,
+        }
+    }
+    /// Whether this binding is triggered by the given key with the given modifiers.
+    /// If ignore_shift is set, bindings without shift are triggered regardless of the shift keys,
+    /// e.g., for keys that are typed with shift on some keyboard layouts.
+    fn matches(&self, key: Keycode, keymod: Mod, ignore_shift: bool) -> bool {
+        let ctrl = keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD);
+        let shift = keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+        self.key == key
+            && self.ctrl == ctrl
+            && (self.shift == shift || (ignore_shift && !self.shift))
+    }
+}
+
+/// Formats the binding like "Ctrl+Shift+S"
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.key.name())
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.trim().split('+').map(str::trim).collect();
+        // A trailing "+" is the plus key itself, e.g. "Ctrl++"
+        if parts.len() > 1 && parts.ends_with(&["", ""]) {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let Some((key, modifiers)) = parts.split_last() else {
+            return Err("Missing key".to_string());
+        };
+        if key.is_empty() {
+            return Err("Missing key".to_string());
+        }
+        let mut binding = KeyBinding::new(Keycode::Escape);
+        for modifier in modifiers {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" => binding.ctrl = true,
+                "shift" => binding.shift = true,
+                _ => return Err(format!("Unknown modifier '{}'", modifier)),
+            }
+        }
+        binding.key = Keycode::from_name(key).ok_or_else(|| format!("Unknown key '{}'", key))?;
+        Ok(binding)
+    }
+}
+
+/// The keyboard shortcuts of all actions
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keybindings {
+    bindings: Vec<(Action, KeyBinding)>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            bindings: Action::all()
+                .map(|action| (action, action.default_binding()))
+                .collect(),
+        }
+    }
+}
+
+impl Keybindings {
+    /// The keybinding file in the user configuration directory
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("keybindings.txt"))
+    }
+    /// Load the keybindings from the keybinding file, returning a message for each invalid line.
+    /// If the file does not exist, the default bindings are used.
+    pub fn load() -> (Self, Vec<String>) {
+        let mut ret = Self::default();
+        let Some(text) = Self::path().and_then(|path| fs::read_to_string(path).ok()) else {
+            return (ret, Vec::new());
+        };
+        let errors = ret.parse(&text);
+        (ret, errors)
+    }
+    /// Write the bindings to the keybinding file so that users can edit it
+    pub fn save(&self) -> Result<PathBuf, String> {
+        let path = Self::path().ok_or_else(|| "No configuration directory found".to_string())?;
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, self.to_string()))
+            .map_err(|e| e.to_string())?;
+        Ok(path)
+    }
+    /// Apply all bindings of the given keybinding file, returning a message for each invalid line
+    fn parse(&mut self, text: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Some((name, binding)) = line.split_once('=') else {
+                errors.push(format!(
+                    "Keybindings line {}: Expected 'action = key'",
+                    number + 1
+                ));
+                continue;
+            };
+            let Some(action) = Action::all().find(|a| a.name() == name.trim()) else {
+                errors.push(format!(
+                    "Keybindings line {}: Unknown action '{}'",
+                    number + 1,
+                    name.trim()
+                ));
+                continue;
+            };
+            match binding.parse() {
+                Ok(binding) => {
+                    for (a, b) in &mut self.bindings {
+                        if *a == action {
+                            *b = binding;
+                        }
+                    }
+                },
+                Err(e) => errors.push(format!("Keybindings line {}: {}", number + 1, e)),
+            }
+        }
+        errors
+    }
+    /// The action triggered by the given key with the given modifiers, if any.
+    /// Bindings that match the shift keys exactly take precedence.
+    pub fn action(&self, key: Keycode, keymod: Mod) -> Option<Action> {
+        [false, true].into_iter().find_map(|ignore_shift| {
+            self.bindings
+                .iter()
+                .find(|(_, binding)| binding.matches(key, keymod, ignore_shift))
+                .map(|(action, _)| *action)
+        })
+    }
+    /// All actions with their current key bindings
+    pub fn bindings(&self) -> &[(Action, KeyBinding)] {
+        &self.bindings
+    }
+}
+
+/// Formats the bindings as keybinding file
+impl Display for Keybindings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "# Sandbox keyboard shortcuts, one 'action = key' per line."
+        )?;
+        writeln!(f, "# Keys may be prefixed with Ctrl+ and Shift+.")?;
+        for (action, binding) in &self.bindings {
+            writeln!(
+                f,
+                "{} = {} # {}",
+                action.name(),
+                binding,
+                action.description()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::keybindings::{Action, KeyBinding, Keybindings};
+    use sdl2::keyboard::{Keycode, Mod};
+
+    #[test]
+    fn test_default_bindings() {
+        let bindings = Keybindings::default();
+        assert_eq!(
+            bindings.action(Keycode::Z, Mod::LCTRLMOD),
+            Some(Action::Undo)
+        );
+        assert_eq!(bindings.action(Keycode::Z, Mod::NOMOD), None);
+        assert_eq!(
+            bindings.action(Keycode::Tab, Mod::RSHIFTMOD),
+            Some(Action::PreviousCursor)
+        );
+    }
+
+    #[test]
+    fn test_keys_without_shift_binding_match_with_shift() {
+        let bindings = Keybindings::default();
+        assert_eq!(
+            bindings.action(Keycode::Num3, Mod::LSHIFTMOD),
+            Some(Action::Element(2))
+        );
+        assert_eq!(
+            bindings.action(Keycode::Z, Mod::LCTRLMOD | Mod::LSHIFTMOD),
+            Some(Action::Undo)
+        );
+        // A binding that requires shift is not triggered without it
+        let mut bindings = Keybindings::default();
+        bindings.bindings[0].1 = KeyBinding {
+            key: Keycode::F2,
+            ctrl: false,
+            shift: true,
+        };
+        assert_eq!(bindings.action(Keycode::F2, Mod::NOMOD), None);
+        assert_eq!(
+            bindings.action(Keycode::F2, Mod::RSHIFTMOD),
+            Some(bindings.bindings[0].0)
+        );
+    }
+
+    #[test]
+    fn test_parse_keybinding_file() {
+        let mut bindings = Keybindings::default();
+        let errors = bindings.parse("# A comment\n\nfly = Ctrl+F\nundo Ctrl+U\nsave = Alt+S\n");
+        assert_eq!(errors.len(), 3);
+        assert_eq!(bindings, Keybindings::default());
+        assert!(errors[0].starts_with("Keybindings line 3:"));
+        assert!(errors[1].starts_with("Keybindings line 4:"));
+        assert!(errors[2].contains("Unknown modifier 'Alt'"));
+    }
+}
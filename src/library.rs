@@ -0,0 +1,184 @@
+//   sandbox - Stamp library
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::config::config_dir;
+use crate::world::snippet::Snippet;
+use std::fs;
+use std::path::PathBuf;
+
+/// The file extension of saved stamps
+const STAMP_EXTENSION: &str = "stamp";
+
+/// A named snippet saved in the stamp library
+#[derive(Clone, Debug, PartialEq)]
+pub struct LibraryStamp {
+    pub name: String,
+    pub snippet: Snippet,
+}
+
+/// The library of saved snippets, stored as one file per stamp in a directory on disk
+#[derive(Clone, Debug, Default)]
+pub struct StampLibrary {
+    /// The directory holding the stamp files, if it is known
+    directory: Option<PathBuf>,
+    /// All loaded stamps, sorted by name
+    stamps: Vec<LibraryStamp>,
+}
+
+impl StampLibrary {
+    /// Load the stamp library from the user configuration directory,
+    /// returning a message for each stamp that could not be loaded
+    pub fn load() -> (Self, Vec<String>) {
+        Self::load_from(config_dir().map(|dir| dir.join("stamps")))
+    }
+    /// Load all stamps from the given directory, skipping files that cannot be read
+    fn load_from(directory: Option<PathBuf>) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut stamps: Vec<LibraryStamp> = directory
+            .iter()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == STAMP_EXTENSION))
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_string_lossy().to_string();
+                match fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|text| text.parse::<Snippet>())
+                {
+                    Ok(snippet) => Some(LibraryStamp { name, snippet }),
+                    Err(e) => {
+                        errors.push(format!("Skipping stamp {}: {}", path.display(), e));
+                        None
+                    },
+                }
+            })
+            .collect();
+        stamps.sort_by(|a, b| a.name.cmp(&b.name));
+        (Self { directory, stamps }, errors)
+    }
+    /// All stamps in the library, sorted by name
+    pub fn stamps(&self) -> &[LibraryStamp] {
+        &self.stamps
+    }
+    /// Save the given snippet under the given name, replacing any stamp with the same name
+    pub fn save(&mut self, name: &str, snippet: &Snippet) -> Result<(), String> {
+        let name = sanitize_name(name)?;
+        let directory = self
+            .directory
+            .as_ref()
+            .ok_or_else(|| "No configuration directory found".to_string())?;
+        fs::create_dir_all(directory).map_err(|e| e.to_string())?;
+        fs::write(self.path(&name)?, snippet.to_string()).map_err(|e| e.to_string())?;
+        self.stamps.retain(|s| s.name != name);
+        let index = self.stamps.partition_point(|s| s.name < name);
+        self.stamps.insert(
+            index,
+            LibraryStamp {
+                name,
+                snippet: snippet.clone(),
+            },
+        );
+        Ok(())
+    }
+    /// Delete the stamp with the given name from the library and from disk
+    pub fn delete(&mut self, name: &str) -> Result<(), String> {
+        fs::remove_file(self.path(name)?).map_err(|e| e.to_string())?;
+        self.stamps.retain(|s| s.name != name);
+        Ok(())
+    }
+    fn path(&self, name: &str) -> Result<PathBuf, String> {
+        self.directory
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.{}", name, STAMP_EXTENSION)))
+            .ok_or_else(|| "No configuration directory found".to_string())
+    }
+}
+
+/// Check that the given stamp name can be used as file name
+fn sanitize_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("The stamp name must not be empty".to_string());
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+    {
+        return Err("The stamp name may only contain letters, digits, spaces, - and _".to_string());
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::library::StampLibrary;
+    use crate::shapes::ShapeKind;
+    use crate::world::GameWorld;
+    use crate::world::brush::BrushSettings;
+    use crate::world::snippet::Selection;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::env;
+
+    #[test]
+    fn test_save_load_and_delete_stamps() {
+        let directory = env::temp_dir().join(format!("sandbox-stamps-{}", std::process::id()));
+        let mut board = GameWorld::new(5, 5);
+        board.draw_shape(
+            &ShapeKind::Line { width: 1 },
+            &[(0, 0), (4, 0)],
+            Element::BrickWall,
+            &BrushSettings::default(),
+            &mut XorShiftRng::seed_from_u64(0),
+        );
+        let snippet = board
+            .copy_selection(&Selection::Rectangle {
+                from: (0, 0),
+                to: (4, 1),
+            })
+            .unwrap();
+        let (mut library, errors) = StampLibrary::load_from(Some(directory.clone()));
+        assert!(errors.is_empty());
+        assert!(library.save("../escape", &snippet).is_err());
+        library.save("Furnace", &snippet).unwrap();
+        let (loaded, errors) = StampLibrary::load_from(Some(directory.clone()));
+        assert!(errors.is_empty());
+        assert_eq!(loaded.stamps(), library.stamps());
+        library.delete("Furnace").unwrap();
+        assert!(
+            StampLibrary::load_from(Some(directory.clone()))
+                .0
+                .stamps()
+                .is_empty()
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_stamps_are_reported() {
+        let directory = env::temp_dir().join(format!("sandbox-bad-stamps-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("Broken.stamp"), "not a stamp").unwrap();
+        let (library, errors) = StampLibrary::load_from(Some(directory.clone()));
+        assert!(library.stamps().is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Broken.stamp"));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
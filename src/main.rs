@@ -15,8 +15,16 @@
 //   along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 mod canvas_display;
+mod codec;
 mod colors;
+mod config;
 mod element;
+mod keybindings;
+mod library;
+mod savegame;
+mod shapes;
+mod stamps;
+mod text;
 mod ui;
 mod world;
 // /// How fast the simulation runs, independently of framerate
@@ -26,17 +34,28 @@ mod world;
 const TOOLTIP_TEXT_DENSITY: Color32 = Color32::from_rgb(0xAA, 0xAA, 0x44);
 const TOOLTIP_TEXT_DESCRIPTION: Color32 = Color32::from_rgb(0x66, 0x66, 0x66);
 
+use crate::colors::BOARD_BACKGROUND_COLOR;
 use crate::element::{Element, ElementKind};
-use crate::ui::{CursorKind, Ui};
-use crate::world::GameWorld;
+use crate::keybindings::{Action, Keybindings};
+use crate::library::StampLibrary;
+use crate::savegame::{load_world, save_world};
+use crate::shapes::ShapeKind;
+use crate::ui::{CursorKind, MAX_BRUSH_SIZE, Ui, ZOOM_LEVELS};
+use crate::world::brush::PenStroke;
+use crate::world::gravity::{AttractorKind, GravityField};
+use crate::world::reactions::REACTIONS;
+use crate::world::snippet::Selection;
+use crate::world::{
+    DEFAULT_WORLD_HEIGHT, DEFAULT_WORLD_WIDTH, GameWorld, MAX_WORLD_SIZE, MIN_WORLD_SIZE,
+};
 use egui::FontFamily::Proportional;
 use egui::{Align, Color32, FontId, Frame, Layout, Margin, RichText, TextStyle, Vec2, Visuals};
 use egui_sdl2_canvas::Painter;
 use itertools::Itertools;
-use rand::SeedableRng;
+use rand::{RngCore, SeedableRng};
 use rand_xorshift::XorShiftRng;
 use sdl2::event::{Event, WindowEvent};
-use sdl2::keyboard::Keycode;
+use sdl2::keyboard::Mod;
 use sdl2::mouse::{MouseButton, MouseState};
 use sdl2::pixels::PixelFormatEnum;
 use std::cmp::max;
@@ -53,7 +72,7 @@ fn main() -> Result<(), String> {
         gl_attr.set_context_profile(sdl2::video::GLProfile::Core);
         gl_attr.set_context_version(1, 1);
     }
-    let mut game_world = Ui::new(1800, 960, 4);
+    let mut game_world = Ui::new(1800, 960, 4, (DEFAULT_WORLD_WIDTH, DEFAULT_WORLD_HEIGHT));
     let window = video_subsystem
         .window(
             "Sandbox",
@@ -94,10 +113,7 @@ fn main() -> Result<(), String> {
         .map_err(|e| e.to_string())?;
     let creator = canvas.texture_creator();
     let mut painter = Painter::new();
-    let mut world: GameWorld = GameWorld::new(
-        game_world.board_width / game_world.scaling_factor,
-        game_world.board_height / game_world.scaling_factor,
-    );
+    let mut world: GameWorld = GameWorld::new(DEFAULT_WORLD_WIDTH, DEFAULT_WORLD_HEIGHT);
     let mut texture = creator
         .create_texture_streaming(
             PixelFormatEnum::ARGB8888,
@@ -111,6 +127,21 @@ fn main() -> Result<(), String> {
 
     let mut prev_x = -1;
     let mut prev_y = -1;
+    let mut drag_start: Option<(i32, i32)> = None;
+    let mut pan_from: Option<(i32, i32)> = None;
+    let mut shape_points: Vec<(i32, i32)> = Vec::new();
+    let mut shape_dragging = false;
+    let mut pen = PenStroke::default();
+    let (mut library, library_errors) = StampLibrary::load();
+    let (keybindings, keybinding_errors) = Keybindings::load();
+    let load_errors = [keybinding_errors, library_errors].concat();
+    if !load_errors.is_empty() {
+        game_world.status_message = Some(load_errors.join("; "));
+    }
+    let mut actions: Vec<Action> = Vec::new();
+    // Whether egui used the keyboard in the last frame, e.g., for a text field
+    let mut keyboard_captured = false;
+    let mut mouse_was_pressed = false;
     let mut over_elem;
 
     let start_time = Instant::now();
@@ -120,21 +151,23 @@ fn main() -> Result<(), String> {
         for event in event_pump.poll_iter() {
             platform.handle_event(&event, &sdl_context, &video_subsystem);
             match event {
-                Event::Quit { .. }
-                | Event::KeyDown {
-                    keycode: Some(Keycode::Escape),
+                Event::Quit { .. } => break 'running,
+                Event::KeyDown {
+                    keycode: Some(key),
+                    keymod,
                     ..
-                } => break 'running,
+                } if !keyboard_captured => match keybindings.action(key, keymod) {
+                    Some(Action::Quit) => break 'running,
+                    Some(action) => actions.push(action),
+                    None => {},
+                },
                 Event::Window {
                     win_event:
                         WindowEvent::Resized(width, height) | WindowEvent::SizeChanged(width, height),
                     ..
                 } => {
-                    game_world = game_world.resize(
-                        &mut world,
-                        max(300, width) as usize,
-                        max(200, height) as usize,
-                    );
+                    game_world =
+                        game_world.resize(max(300, width) as usize, max(200, height) as usize);
                     texture = creator
                         .create_texture_streaming(
                             PixelFormatEnum::ARGB8888,
@@ -143,12 +176,79 @@ fn main() -> Result<(), String> {
                         )
                         .unwrap();
                 },
+                Event::MouseWheel {
+                    y,
+                    mouse_x,
+                    mouse_y,
+                    ..
+                } if y != 0 && game_world.is_over_board(mouse_x, mouse_y) => {
+                    // Shift + mouse wheel resizes the brush, the mouse wheel alone zooms
+                    if sdl_context
+                        .keyboard()
+                        .mod_state()
+                        .intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD)
+                    {
+                        game_world.resize_cursor(y.signum());
+                    } else {
+                        game_world.zoom_at(mouse_x, mouse_y, y.signum());
+                    }
+                },
                 _ => {},
             }
         }
+        for action in actions.drain(..) {
+            handle_action(action, &mut game_world, &mut world, &mut current_elem);
+        }
         // Always handle mouse events, no matter if the mouse is moved
         let state = MouseState::new(&event_pump);
-        if state.is_mouse_button_pressed(MouseButton::Left) {
+        // Remember the world before each click on the board, so that the change can be undone
+        let mouse_pressed = state.is_mouse_button_pressed(MouseButton::Left)
+            || state.is_mouse_button_pressed(MouseButton::Right);
+        if mouse_pressed
+            && !mouse_was_pressed
+            && game_world.is_over_board(state.x(), state.y())
+            && !matches!(game_world.cursor(), CursorKind::Select { .. })
+        {
+            world.checkpoint();
+        }
+        mouse_was_pressed = mouse_pressed;
+        // Pan the camera while the middle mouse button is held down
+        if state.is_mouse_button_pressed(MouseButton::Middle) {
+            if let Some((from_x, from_y)) = pan_from {
+                game_world.pan(state.x() - from_x, state.y() - from_y);
+            }
+            pan_from = Some((state.x(), state.y()));
+        } else {
+            pan_from = None;
+        }
+        if let CursorKind::Shape { shape } = *game_world.cursor() {
+            handle_shape_tool(
+                &state,
+                &game_world,
+                &mut world,
+                shape,
+                &mut shape_points,
+                &mut shape_dragging,
+                current_elem,
+                &mut rng,
+            );
+        } else if matches!(
+            game_world.cursor(),
+            CursorKind::Select { .. } | CursorKind::Paste
+        ) {
+            shape_points.clear();
+            handle_selection_tool(&state, &mut game_world, &mut world, &mut shape_dragging);
+        } else if game_world.cursor().is_placement_tool() {
+            shape_points.clear();
+            handle_placement_tool(
+                &state,
+                &game_world,
+                &mut world,
+                &mut drag_start,
+                current_elem,
+            );
+        } else if state.is_mouse_button_pressed(MouseButton::Left) {
+            shape_points.clear();
             world.insert_element_at(
                 &game_world,
                 state.x(),
@@ -156,6 +256,8 @@ fn main() -> Result<(), String> {
                 current_elem,
                 prev_x,
                 prev_y,
+                &mut pen,
+                &mut rng,
             );
             prev_x = state.x();
             prev_y = state.y();
@@ -167,18 +269,22 @@ fn main() -> Result<(), String> {
                 Element::None,
                 prev_x,
                 prev_y,
+                &mut pen,
+                &mut rng,
             );
             prev_x = state.x();
             prev_y = state.y();
         } else {
             prev_x = -1;
             prev_y = -1;
+            world.end_pen(&mut pen, &game_world.brush, &mut rng);
         }
         over_elem = world.get_element_at(&game_world, state.x(), state.y());
 
         // let no_ticks = TICKS_PER_SECOND as f32 * delta_s;
-        // Tick once for scaling 4, 4x for scaling 1
-        for _ in 0..(5i32 - game_world.scaling_factor as i32).max(1) {
+        // The whole world is simulated, independently of the zoom level
+        let step = std::mem::take(&mut game_world.step);
+        if !game_world.paused || step {
             world.tick(&mut rng);
         }
 
@@ -186,8 +292,15 @@ fn main() -> Result<(), String> {
         let ctx = platform.context();
         build_element_buttons(&ctx, &game_world, &mut current_elem);
         build_top_settings_pane(&ctx, &mut game_world, &mut world);
-        build_bottom_status_pane(&ctx, &mut game_world, over_elem);
+        build_reaction_encyclopedia(&ctx, &mut game_world);
+        build_new_world_dialog(&ctx, &mut game_world, &mut world);
+        build_stamp_library(&ctx, &mut game_world, &mut library);
+        build_element_search(&ctx, &mut game_world, &mut current_elem);
+        build_help_overlay(&ctx, &mut game_world, &keybindings);
+        let over_oxygen = world.get_oxygen_at(&game_world, state.x(), state.y());
+        build_bottom_status_pane(&ctx, &mut game_world, &world, over_elem, over_oxygen);
         build_left_buttons(&ctx, &mut game_world, &mut world);
+        keyboard_captured = ctx.wants_keyboard_input();
 
         let output = platform.end_frame(&mut video_subsystem).unwrap();
         let v_primitives = platform.tessellate(&output);
@@ -195,7 +308,28 @@ fn main() -> Result<(), String> {
         // Update the window graphics
         // Draw the new board to the window
         game_world.draw(&mut canvas, &mut texture, &world)?;
-        game_world.draw_mouse_preview_at(&mut canvas, state.x(), state.y())?;
+        game_world.draw_gravity_fields(&mut canvas, &world)?;
+        if let Some(start) = drag_start
+            && matches!(
+                game_world.cursor(),
+                CursorKind::GravityZone | CursorKind::RigidBody
+            )
+        {
+            game_world.draw_area_preview(&mut canvas, start, (state.x(), state.y()))?;
+        } else if let CursorKind::Shape { shape } = game_world.cursor()
+            && !shape_points.is_empty()
+        {
+            let mut points = shape_points.clone();
+            points.extend(game_world.window_to_board_coordinate(state.x(), state.y()));
+            game_world.draw_shape_preview(&mut canvas, shape, &points)?;
+        } else if let CursorKind::Select { .. } = game_world.cursor() {
+            game_world.draw_selection(&mut canvas)?;
+            game_world.draw_mouse_preview_at(&mut canvas, state.x(), state.y(), &pen)?;
+        } else if let CursorKind::Paste = game_world.cursor() {
+            game_world.draw_paste_preview(&mut canvas, state.x(), state.y())?;
+        } else {
+            game_world.draw_mouse_preview_at(&mut canvas, state.x(), state.y(), &pen)?;
+        }
 
         // Render imgui
         canvas.window_mut().gl_make_current(&gl_context)?;
@@ -283,20 +417,735 @@ fn build_left_buttons(context: &egui::Context, game_world: &mut Ui, board: &mut
                 .inner_margin(Margin::same(context.style().spacing.item_spacing.x)),
         )
         .show(context, |ui| {
-            ui.label("Scale:");
+            ui.label("World:");
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.horizontal_wrapped(|ui| {
+                if ui.button("Undo").clicked() {
+                    handle_action(Action::Undo, game_world, board, &mut Element::None);
+                }
+                if ui.button("Save").clicked() {
+                    handle_action(Action::Save, game_world, board, &mut Element::None);
+                }
+                if ui.button("Load").clicked() {
+                    handle_action(Action::Load, game_world, board, &mut Element::None);
+                }
+            });
+            ui.separator();
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.label("Zoom:");
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.horizontal_wrapped(|ui| {
+                for s in &ZOOM_LEVELS {
+                    let selected = game_world.scaling_factor;
+                    let mut is_selected = *s == selected;
+                    let tv = ui.toggle_value(&mut is_selected, format!("{}", s));
+                    if tv.clicked() && is_selected {
+                        game_world.rescale(s);
+                    }
+                    tv.on_hover_text_at_pointer(format!("Set the pixel scale to {}x{}", s, s));
+                }
+            });
+            ui.label("Scroll to zoom, drag with the middle mouse button to pan.");
+            build_minimap(ui, game_world, board);
+            ui.separator();
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.label("Brush:");
+            ui.add_space(ui.spacing().item_spacing.y);
+            if let Some(size) = game_world.cursor().size() {
+                let mut new_size = size;
+                ui.add(egui::Slider::new(&mut new_size, 1..=MAX_BRUSH_SIZE).text("Size"))
+                    .on_hover_text_at_pointer("The brush size. Hold shift and scroll to resize.");
+                if new_size != size {
+                    let cursor = game_world.cursor().with_size(new_size);
+                    game_world.set_cursor(cursor);
+                }
+            }
+            build_brush_settings(ui, game_world);
+            ui.separator();
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.label("Replace:");
+            ui.add_space(ui.spacing().item_spacing.y);
+            egui::ComboBox::from_id_source("ReplaceSource")
+                .selected_text(game_world.replace_source.to_string())
+                .show_ui(ui, |ui| {
+                    for e in Element::iter().filter(|e| e.show_in_ui()) {
+                        ui.selectable_value(&mut game_world.replace_source, e, e.to_string());
+                    }
+                })
+                .response
+                .on_hover_text_at_pointer("The element that is painted over by the replace brush");
+            ui.separator();
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.label("Text:");
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.add(egui::TextEdit::multiline(&mut game_world.text).desired_rows(2))
+                .on_hover_text_at_pointer("The text written by the text tool");
+            ui.separator();
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.label("Selection:");
+            ui.add_space(ui.spacing().item_spacing.y);
+            build_selection_buttons(ui, game_world, board);
+            ui.separator();
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.label("Gravity:");
             ui.add_space(ui.spacing().item_spacing.y);
-            for s in &[1, 2, 4, 6, 8] {
-                let selected = game_world.scaling_factor;
-                let mut is_selected = *s == selected;
-                let tv = ui.toggle_value(&mut is_selected, format!("{}", s));
+            ui.checkbox(&mut game_world.show_gravity_fields, "Show fields")
+                .on_hover_text_at_pointer("Show an overlay of all gravity zones and attractors");
+            ui.label("Zone direction:");
+            ui.horizontal_wrapped(|ui| {
+                for (label, gravity) in [
+                    ("↑", (0.0, -1.0)),
+                    ("↓", (0.0, 1.0)),
+                    ("←", (-1.0, 0.0)),
+                    ("→", (1.0, 0.0)),
+                    ("0", (0.0, 0.0)),
+                ] {
+                    let mut is_selected = game_world.zone_gravity == gravity;
+                    let tv = ui.toggle_value(&mut is_selected, label);
+                    if tv.clicked() && is_selected {
+                        game_world.zone_gravity = gravity;
+                    }
+                }
+            });
+            ui.label("Attractor kind:");
+            for kind in AttractorKind::iter() {
+                let mut is_selected = game_world.attractor_kind == kind;
+                let tv = ui.toggle_value(&mut is_selected, format!("{}", kind));
                 if tv.clicked() && is_selected {
-                    game_world.rescale(board, s);
+                    game_world.attractor_kind = kind;
                 }
-                tv.on_hover_text_at_pointer(format!("Set the pixel scale to {}x{}", s, s));
+                tv.on_hover_text_at_pointer(kind.ui_description());
             }
+            ui.add(
+                egui::Slider::new(&mut game_world.attractor_strength, 0.5..=5.0).text("Strength"),
+            );
+            let clr = ui.button("Clear fields");
+            if clr.clicked() {
+                board.clear_gravity_fields();
+            }
+            clr.on_hover_text_at_pointer("Remove all gravity zones and attractors");
             ui.separator();
             ui.add_space(ui.spacing().item_spacing.y);
+            ui.label("Physics:");
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.add(egui::Slider::new(board.liquid_drag_mut(), 0.0..=0.9).text("Drag"))
+                .on_hover_text_at_pointer(
+                    "The drag of liquids that slows down floating and sinking particles and bodies",
+                );
+            ui.separator();
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.label("Population:");
+            ui.add_space(ui.spacing().item_spacing.y);
+            build_population_graph(ui, board);
+            ui.separator();
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.label("Chemistry:");
+            ui.add_space(ui.spacing().item_spacing.y);
+            ui.toggle_value(&mut game_world.show_reactions, "Reactions")
+                .on_hover_text_at_pointer("Show an encyclopedia of all chemical reactions");
+            ui.separator();
+            ui.add_space(ui.spacing().item_spacing.y);
+        });
+}
+/// Show the modifiers of the square, circle and pen brushes
+fn build_brush_settings(ui: &mut egui::Ui, game_world: &mut Ui) {
+    let brush = &mut game_world.brush;
+    ui.checkbox(&mut brush.spray, "Spray")
+        .on_hover_text_at_pointer("Only paint a random fraction of the covered cells");
+    if brush.spray {
+        ui.add(egui::Slider::new(&mut brush.spray_density, 0.01..=1.0).text("Density"));
+    }
+    ui.checkbox(&mut brush.only_empty, "Only empty cells")
+        .on_hover_text_at_pointer("Only paint into empty cells");
+    ui.checkbox(&mut brush.protect_solids, "Protect solids")
+        .on_hover_text_at_pointer("Never paint over solid elements like walls");
+    ui.label("Mix:")
+        .on_hover_text_at_pointer("Randomly mix other elements into the painted element");
+    let mut removed = None;
+    for (i, (element, share)) in brush.mix.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source(("BrushMix", i))
+                .selected_text(element.to_string())
+                .width(90.0)
+                .show_ui(ui, |ui| {
+                    for e in Element::iter().filter(|e| e.show_in_ui()) {
+                        ui.selectable_value(element, e, e.to_string());
+                    }
+                });
+            ui.add(
+                egui::DragValue::new(share)
+                    .clamp_range(0.0..=1.0)
+                    .speed(0.01)
+                    .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+            );
+            if ui.small_button("✖").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+    if let Some(i) = removed {
+        brush.mix.remove(i);
+    }
+    if ui.button("Add element").clicked() {
+        brush.mix.push((Element::Salt, 0.3));
+    }
+}
+/// All elements in the order of the element panel
+fn ui_elements() -> impl Iterator<Item = Element> {
+    ElementKind::iter()
+        .filter(|kind| !matches!(kind, ElementKind::None))
+        .flat_map(|kind| Element::iter().filter(move |e| e.is_kind_of(&kind) && e.show_in_ui()))
+}
+/// Perform the action of a keyboard shortcut
+fn handle_action(
+    action: Action,
+    game_world: &mut Ui,
+    world: &mut GameWorld,
+    current_elem: &mut Element,
+) {
+    match action {
+        // Quitting is handled by the event loop
+        Action::Quit => {},
+        Action::Help => game_world.show_help = !game_world.show_help,
+        Action::Pause => game_world.paused = !game_world.paused,
+        Action::Step => game_world.step = true,
+        Action::Reset => {
+            world.checkpoint();
+            world.reset();
+        },
+        Action::Undo => {
+            if !world.undo() {
+                game_world.status_message = Some("Nothing to undo".to_string());
+            }
+        },
+        Action::Save => {
+            game_world.status_message = Some(match save_world(world) {
+                Ok(path) => format!("Saved to {}", path.display()),
+                Err(e) => format!("Could not save: {}", e),
+            });
+        },
+        Action::Load => match load_world() {
+            Ok(loaded) => {
+                *world = loaded;
+                game_world.set_world_size((world.viewport_width(), world.viewport_height()));
+                game_world.status_message = Some("Loaded the saved world".to_string());
+            },
+            Err(e) => game_world.status_message = Some(format!("Could not load: {}", e)),
+        },
+        Action::ZoomIn => game_world.zoom(1),
+        Action::ZoomOut => game_world.zoom(-1),
+        Action::NextCursor => game_world.cycle_cursor(1),
+        Action::PreviousCursor => game_world.cycle_cursor(-1),
+        Action::GrowBrush => game_world.resize_cursor(1),
+        Action::ShrinkBrush => game_world.resize_cursor(-1),
+        Action::SearchElement => game_world.element_search = Some(String::new()),
+        Action::Element(index) => {
+            if let Some(element) = ui_elements().nth(index) {
+                *current_elem = element;
+            }
+        },
+    }
+}
+/// Show a window to search an element by name. Enter selects the first match.
+fn build_element_search(context: &egui::Context, game_world: &mut Ui, selected: &mut Element) {
+    let Some(mut query) = game_world.element_search.take() else {
+        return;
+    };
+    let mut open = true;
+    let mut chosen = None;
+    egui::Window::new("Search Element")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(context, |ui| {
+            let edit = ui.add(egui::TextEdit::singleline(&mut query).hint_text("Element name"));
+            if query.is_empty() {
+                edit.request_focus();
+            }
+            let lowercase = query.to_lowercase();
+            let matches: Vec<Element> = ui_elements()
+                .filter(|e| e.to_string().to_lowercase().contains(&lowercase))
+                .collect();
+            if edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                chosen = matches.first().copied();
+            }
+            ui.horizontal_wrapped(|ui| {
+                for e in matches {
+                    if ui.button(e.to_string()).clicked() {
+                        chosen = Some(e);
+                    }
+                }
+            });
         });
+    if let Some(element) = chosen {
+        *selected = element;
+    }
+    game_world.element_search = (open && chosen.is_none()).then_some(query);
+}
+/// Show a window listing all keyboard shortcuts
+fn build_help_overlay(context: &egui::Context, game_world: &mut Ui, keybindings: &Keybindings) {
+    let mut status_message = None;
+    egui::Window::new("Keyboard Shortcuts")
+        .open(&mut game_world.show_help)
+        .resizable(false)
+        .collapsible(false)
+        .show(context, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("Keybindings").striped(true).show(ui, |ui| {
+                    for (action, binding) in keybindings.bindings() {
+                        ui.label(action.description());
+                        ui.label(RichText::new(binding.to_string()).strong());
+                        ui.end_row();
+                    }
+                    for (description, binding) in [
+                        ("Zoom", "Mouse wheel"),
+                        ("Resize the brush", "Shift+Mouse wheel"),
+                        ("Move the view", "Middle mouse button"),
+                    ] {
+                        ui.label(description);
+                        ui.label(RichText::new(binding).strong());
+                        ui.end_row();
+                    }
+                });
+            });
+            if let Some(path) = Keybindings::path() {
+                ui.separator();
+                ui.label(
+                    RichText::new(format!(
+                        "Edit {} and restart to change the shortcuts.",
+                        path.display()
+                    ))
+                    .color(TOOLTIP_TEXT_DESCRIPTION),
+                );
+                if !path.exists() && ui.button("Create the keybinding file").clicked() {
+                    status_message = Some(match keybindings.save() {
+                        Ok(path) => format!("Wrote {}", path.display()),
+                        Err(e) => format!("Could not write the keybinding file: {}", e),
+                    });
+                }
+            }
+        });
+    if status_message.is_some() {
+        game_world.status_message = status_message;
+    }
+}
+/// Show the clipboard operations on the current selection and the copied cells
+fn build_selection_buttons(ui: &mut egui::Ui, game_world: &mut Ui, board: &mut GameWorld) {
+    ui.horizontal_wrapped(|ui| {
+        let has_selection = game_world.selection.is_some();
+        let copy = ui
+            .add_enabled(has_selection, egui::Button::new("Copy"))
+            .on_hover_text_at_pointer("Copy the selected cells");
+        let cut = ui
+            .add_enabled(has_selection, egui::Button::new("Cut"))
+            .on_hover_text_at_pointer("Copy and remove the selected cells");
+        if (copy.clicked() || cut.clicked())
+            && let Some(selection) = game_world.selection.take()
+        {
+            if cut.clicked() {
+                board.checkpoint();
+            }
+            if let Some(snippet) = board.copy_selection(&selection) {
+                game_world.clipboard = Some(snippet);
+                game_world.set_cursor(CursorKind::Paste);
+            }
+            if cut.clicked() {
+                board.clear_selection(&selection);
+            }
+        }
+        let has_clipboard = game_world.clipboard.is_some();
+        let paste = ui
+            .add_enabled(has_clipboard, egui::Button::new("Paste"))
+            .on_hover_text_at_pointer("Click on the board to paste the copied cells");
+        if paste.clicked() {
+            game_world.set_cursor(CursorKind::Paste);
+        }
+        let clipboard = &mut game_world.clipboard;
+        if ui
+            .add_enabled(has_clipboard, egui::Button::new("↔"))
+            .on_hover_text_at_pointer("Flip the copied cells horizontally")
+            .clicked()
+        {
+            clipboard.iter_mut().for_each(|s| s.flip_horizontal());
+        }
+        if ui
+            .add_enabled(has_clipboard, egui::Button::new("↕"))
+            .on_hover_text_at_pointer("Flip the copied cells vertically")
+            .clicked()
+        {
+            clipboard.iter_mut().for_each(|s| s.flip_vertical());
+        }
+        if ui
+            .add_enabled(has_clipboard, egui::Button::new("⟳"))
+            .on_hover_text_at_pointer("Rotate the copied cells clockwise")
+            .clicked()
+        {
+            clipboard.iter_mut().for_each(|s| s.rotate());
+        }
+    });
+    ui.toggle_value(&mut game_world.show_library, "Stamp library")
+        .on_hover_text_at_pointer("Save the copied cells and reuse saved builds");
+}
+/// Show a window to save the copied cells to the stamp library and to paste saved stamps
+fn build_stamp_library(context: &egui::Context, game_world: &mut Ui, library: &mut StampLibrary) {
+    let mut open = game_world.show_library;
+    egui::Window::new("Stamp Library")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(context, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut game_world.stamp_name)
+                        .hint_text("Name")
+                        .desired_width(120.0),
+                );
+                let save = ui
+                    .add_enabled(game_world.clipboard.is_some(), egui::Button::new("Save"))
+                    .on_hover_text_at_pointer("Save the copied cells under this name");
+                if save.clicked()
+                    && let Some(snippet) = &game_world.clipboard
+                {
+                    game_world.library_error = library.save(&game_world.stamp_name, snippet).err();
+                }
+            });
+            if let Some(error) = &game_world.library_error {
+                ui.label(RichText::new(error).color(Color32::RED));
+            }
+            ui.separator();
+            if library.stamps().is_empty() {
+                ui.label(
+                    RichText::new("No saved stamps yet. Select and copy a build to save it.")
+                        .color(TOOLTIP_TEXT_DESCRIPTION),
+                );
+            }
+            let mut deleted = None;
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for stamp in library.stamps() {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button(&stamp.name)
+                            .on_hover_text_at_pointer("Paste this stamp")
+                            .clicked()
+                        {
+                            game_world.clipboard = Some(stamp.snippet.clone());
+                            game_world.set_cursor(CursorKind::Paste);
+                        }
+                        ui.label(
+                            RichText::new(format!(
+                                "{}x{}",
+                                stamp.snippet.width(),
+                                stamp.snippet.height()
+                            ))
+                            .color(TOOLTIP_TEXT_DESCRIPTION),
+                        );
+                        if ui.small_button("✖").clicked() {
+                            deleted = Some(stamp.name.clone());
+                        }
+                    });
+                }
+            });
+            if let Some(name) = deleted {
+                game_world.library_error = library.delete(&name).err();
+            }
+        });
+    game_world.show_library = open;
+}
+/// Show a dialog to create a new, empty world of a chosen size
+fn build_new_world_dialog(context: &egui::Context, game_world: &mut Ui, board: &mut GameWorld) {
+    let Some((mut width, mut height)) = game_world.new_world_size else {
+        return;
+    };
+    let mut open = true;
+    let mut close = false;
+    egui::Window::new("New World")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(context, |ui| {
+            ui.add(
+                egui::DragValue::new(&mut width)
+                    .clamp_range(MIN_WORLD_SIZE..=MAX_WORLD_SIZE)
+                    .prefix("Width: "),
+            );
+            ui.add(
+                egui::DragValue::new(&mut height)
+                    .clamp_range(MIN_WORLD_SIZE..=MAX_WORLD_SIZE)
+                    .prefix("Height: "),
+            );
+            ui.label(
+                RichText::new("The current world will be deleted.").color(TOOLTIP_TEXT_DESCRIPTION),
+            );
+            ui.horizontal(|ui| {
+                if ui.button("Create").clicked() {
+                    *board = GameWorld::new(width, height);
+                    game_world.set_world_size((width, height));
+                    close = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    close = true;
+                }
+            });
+        });
+    game_world.new_world_size = (open && !close).then_some((width, height));
+}
+/// Show a window listing all reactions of the reaction table
+fn build_reaction_encyclopedia(context: &egui::Context, game_world: &mut Ui) {
+    egui::Window::new("Reactions")
+        .open(&mut game_world.show_reactions)
+        .resizable(false)
+        .collapsible(false)
+        .show(context, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for reaction in REACTIONS {
+                    ui.label(RichText::new(reaction.name).strong());
+                    ui.label(reaction.formula());
+                    if let Some(catalyst) = reaction.catalyst {
+                        ui.label(format!("Catalyst: {}", catalyst));
+                    }
+                    if let Some(min_temperature) = reaction.min_temperature {
+                        ui.label(format!("Requires at least {}°C", min_temperature));
+                    }
+                    match reaction.energy {
+                        e if e > 0 => ui.label("Exothermic"),
+                        e if e < 0 => ui.label("Endothermic"),
+                        _ => ui.label("Thermally neutral"),
+                    };
+                    ui.label(RichText::new(reaction.description).color(TOOLTIP_TEXT_DESCRIPTION));
+                    ui.separator();
+                }
+            });
+        });
+}
+/// Show an overview of the whole world with the visible area highlighted.
+/// Clicking or dragging on the map moves the camera.
+fn build_minimap(ui: &mut egui::Ui, game_world: &mut Ui, board: &GameWorld) {
+    const BLOCK_SIZE: f32 = 2.0;
+    let (world_width, world_height) = (board.viewport_width(), board.viewport_height());
+    let width = ui.available_width();
+    let height = width * world_height as f32 / world_width as f32;
+    let (rect, response) =
+        ui.allocate_exact_size(Vec2::new(width, height), egui::Sense::click_and_drag());
+    let painter = ui.painter_at(rect);
+    let background = BOARD_BACKGROUND_COLOR;
+    painter.rect_filled(
+        rect,
+        0.0,
+        Color32::from_rgb(background.r, background.g, background.b),
+    );
+    // Sample one cell per block, skipping empty cells
+    let columns = (width / BLOCK_SIZE) as usize;
+    let rows = (height / BLOCK_SIZE) as usize;
+    for row in 0..rows {
+        for column in 0..columns {
+            let x = column * world_width / columns;
+            let y = row * world_height / rows;
+            if board.board()[x][y] == Element::None {
+                continue;
+            }
+            let color = board.color_at(x, y);
+            painter.rect_filled(
+                egui::Rect::from_min_size(
+                    rect.min + Vec2::new(column as f32, row as f32) * BLOCK_SIZE,
+                    Vec2::splat(BLOCK_SIZE),
+                ),
+                0.0,
+                Color32::from_rgb(color.r, color.g, color.b),
+            );
+        }
+    }
+    let scale = width / world_width as f32;
+    let (view_x, view_y, view_width, view_height) = game_world.visible_area();
+    painter.rect_stroke(
+        egui::Rect::from_min_size(
+            rect.min + Vec2::new(view_x, view_y) * scale,
+            Vec2::new(view_width, view_height) * scale,
+        ),
+        0.0,
+        egui::Stroke::new(1.0, Color32::WHITE),
+    );
+    if let Some(pos) = response.interact_pointer_pos() {
+        let target = (pos - rect.min) / scale;
+        game_world.center_camera_on(target.x, target.y);
+    }
+    response.on_hover_text_at_pointer("Click or drag to move the view");
+}
+/// Show the current creature population and a graph of its history
+fn build_population_graph(ui: &mut egui::Ui, board: &GameWorld) {
+    let history = board.population_history();
+    let latest = history.back().copied().unwrap_or_default();
+    for (creature, count) in latest.counts() {
+        let color = creature.color();
+        ui.label(
+            RichText::new(format!("{}: {}", creature, count))
+                .color(Color32::from_rgb(color.r, color.g, color.b)),
+        );
+    }
+    let (rect, _) =
+        ui.allocate_exact_size(Vec2::new(ui.available_width(), 60.0), egui::Sense::hover());
+    ui.painter()
+        .rect_stroke(rect, 0.0, egui::Stroke::new(1.0, Color32::GRAY));
+    let max_count = history
+        .iter()
+        .flat_map(|p| p.counts().map(|(_, count)| count))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let step = rect.width() / max(history.len(), 2).saturating_sub(1) as f32;
+    for species in 0..latest.counts().len() {
+        let color = latest.counts()[species].0.color();
+        let points = history
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                egui::pos2(
+                    rect.left() + i as f32 * step,
+                    rect.bottom() - p.counts()[species].1 as f32 / max_count as f32 * rect.height(),
+                )
+            })
+            .collect();
+        ui.painter().add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.0, Color32::from_rgb(color.r, color.g, color.b)),
+        ));
+    }
+}
+/// Draw shapes between the points where the left mouse button is pressed and released.
+/// Polygons are placed point by point with left clicks and drawn with a right click.
+fn handle_shape_tool(
+    state: &MouseState,
+    game_world: &Ui,
+    world: &mut GameWorld,
+    shape: ShapeKind,
+    points: &mut Vec<(i32, i32)>,
+    dragging: &mut bool,
+    current_elem: Element,
+    rng: &mut dyn RngCore,
+) {
+    let pressed = state.is_mouse_button_pressed(MouseButton::Left);
+    let position = game_world.window_to_board_coordinate(state.x(), state.y());
+    if shape.is_multi_point() {
+        if pressed && !*dragging {
+            points.extend(position);
+        }
+        if state.is_mouse_button_pressed(MouseButton::Right) && !points.is_empty() {
+            world.draw_shape(&shape, points, current_elem, &game_world.brush, rng);
+            points.clear();
+        }
+    } else if pressed && !*dragging {
+        points.clear();
+        points.extend(position);
+    } else if !pressed && *dragging {
+        points.extend(position);
+        world.draw_shape(&shape, points, current_elem, &game_world.brush, rng);
+        points.clear();
+    }
+    *dragging = pressed;
+}
+/// Select cells by dragging a rectangle or drawing the outline of a lasso selection,
+/// or paste the clipboard with a left click. A right click clears the selection.
+fn handle_selection_tool(
+    state: &MouseState,
+    game_world: &mut Ui,
+    world: &mut GameWorld,
+    dragging: &mut bool,
+) {
+    let pressed = state.is_mouse_button_pressed(MouseButton::Left);
+    let position = game_world.window_to_board_coordinate(state.x(), state.y());
+    match (*game_world.cursor(), position) {
+        (CursorKind::Paste, Some((x, y))) if pressed && !*dragging => {
+            if let Some(snippet) = &game_world.clipboard {
+                world.paste(snippet, x, y);
+            }
+        },
+        (CursorKind::Select { lasso }, Some(position)) if pressed => {
+            match (&mut game_world.selection, *dragging) {
+                (Some(Selection::Rectangle { to, .. }), true) => *to = position,
+                (Some(Selection::Lasso { path }), true) => {
+                    if path.last() != Some(&position) {
+                        path.push(position);
+                    }
+                },
+                _ => {
+                    game_world.selection = Some(match lasso {
+                        true => Selection::Lasso {
+                            path: vec![position],
+                        },
+                        false => Selection::Rectangle {
+                            from: position,
+                            to: position,
+                        },
+                    })
+                },
+            }
+        },
+        (CursorKind::Select { .. }, _) if state.is_mouse_button_pressed(MouseButton::Right) => {
+            game_world.selection = None;
+        },
+        _ => {},
+    }
+    *dragging = pressed;
+}
+/// Place gravity fields and rigid bodies on left mouse button release
+/// and remove gravity fields on right mouse button press
+fn handle_placement_tool(
+    state: &MouseState,
+    game_world: &Ui,
+    world: &mut GameWorld,
+    drag_start: &mut Option<(i32, i32)>,
+    current_elem: Element,
+) {
+    if state.is_mouse_button_pressed(MouseButton::Left) {
+        if drag_start.is_none() {
+            *drag_start = Some((state.x(), state.y()));
+        }
+        return;
+    }
+    if let Some((start_x, start_y)) = drag_start.take()
+        && let Some((x1, y1)) = game_world.window_to_board_coordinate(start_x, start_y)
+    {
+        match game_world.cursor() {
+            CursorKind::GravityZone => {
+                if let Some((x2, y2)) = game_world.window_to_board_coordinate(state.x(), state.y())
+                {
+                    world.add_gravity_field(GravityField::Zone {
+                        x: x1.min(x2) as usize,
+                        y: y1.min(y2) as usize,
+                        width: x1.abs_diff(x2) as usize + 1,
+                        height: y1.abs_diff(y2) as usize + 1,
+                        gravity: game_world.zone_gravity,
+                    });
+                }
+            },
+            CursorKind::RigidBody => {
+                if let Some((x2, y2)) = game_world.window_to_board_coordinate(state.x(), state.y())
+                {
+                    world.create_rigid_bodies(
+                        x1.min(x2) as usize,
+                        y1.min(y2) as usize,
+                        x1.max(x2) as usize,
+                        y1.max(y2) as usize,
+                    );
+                }
+            },
+            CursorKind::Attractor { radius } => {
+                world.add_gravity_field(GravityField::Attractor {
+                    x: x1 as usize,
+                    y: y1 as usize,
+                    radius: *radius as usize,
+                    strength: game_world.attractor_strength,
+                    kind: game_world.attractor_kind,
+                    emits: current_elem,
+                });
+            },
+            _ => {},
+        }
+    }
+    if state.is_mouse_button_pressed(MouseButton::Right)
+        && let Some((x, y)) = game_world.window_to_board_coordinate(state.x(), state.y())
+    {
+        world.remove_gravity_fields_at(x as usize, y as usize);
+    }
 }
 fn build_top_settings_pane(context: &egui::Context, game_world: &mut Ui, board: &mut GameWorld) {
     let buttonbar_height = game_world.top_buttonbar_height();
@@ -317,9 +1166,25 @@ fn build_top_settings_pane(context: &egui::Context, game_world: &mut Ui, board:
                     ui.with_layout(Layout::left_to_right(Align::Max), |ui| {
                         let rst = ui.button("Reset");
                         if rst.clicked() {
+                            board.checkpoint();
                             board.reset();
                         }
                         rst.on_hover_text_at_pointer("Reset the game board (delete everything)");
+                        let new = ui.button("New");
+                        if new.clicked() {
+                            game_world.new_world_size =
+                                Some((board.viewport_width(), board.viewport_height()));
+                        }
+                        new.on_hover_text_at_pointer("Create a new world with a different size");
+                        ui.toggle_value(&mut game_world.paused, "⏸")
+                            .on_hover_text_at_pointer("Pause the simulation");
+                        let step = ui.add_enabled(game_world.paused, egui::Button::new("⏭"));
+                        if step.clicked() {
+                            game_world.step = true;
+                        }
+                        step.on_hover_text_at_pointer("Simulate a single tick");
+                        ui.toggle_value(&mut game_world.show_help, "?")
+                            .on_hover_text_at_pointer("Show the keyboard shortcuts");
                     });
                 });
                 // Start items at left board edge
@@ -352,7 +1217,9 @@ fn build_top_settings_pane(context: &egui::Context, game_world: &mut Ui, board:
 fn build_bottom_status_pane(
     context: &egui::Context,
     game_world: &mut Ui,
+    world: &GameWorld,
     over_elem: Option<Element>,
+    over_oxygen: Option<u8>,
 ) {
     let status_height = game_world.bottom_statusbar_height();
     egui::TopBottomPanel::bottom("StatusBar")
@@ -373,8 +1240,28 @@ fn build_bottom_status_pane(
                     ui.separator();
                     if let Some(elem) = over_elem {
                         ui.label(format!("Over {}", elem));
+                        if let Some(oxygen) = over_oxygen
+                            && matches!(elem, Element::None | Element::Oxygen)
+                        {
+                            ui.label(format!("Oxygen {}%", oxygen));
+                        }
+                        if let Element::SaltWater { concentration } = elem {
+                            ui.label(format!("Salinity {}%", concentration.0));
+                        }
                     }
                     ui.separator();
+                    if game_world.paused {
+                        ui.label("Paused");
+                        ui.separator();
+                    }
+                    if let Some(message) = &game_world.status_message {
+                        ui.label(message);
+                        ui.separator();
+                    }
+                    if !world.rigid_bodies().is_empty() {
+                        ui.label(format!("{} rigid bodies", world.rigid_bodies().len()));
+                        ui.separator();
+                    }
                 });
             });
         });
@@ -0,0 +1,141 @@
+//   sandbox - Saving and loading worlds
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::config::config_dir;
+use crate::world::GameWorld;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The file the world is saved to and loaded from
+fn save_path() -> Result<PathBuf, String> {
+    config_dir()
+        .map(|dir| dir.join("saves").join("quicksave.world"))
+        .ok_or_else(|| "No configuration directory found".to_string())
+}
+
+/// Save the complete state of the given world, returning the path of the save file
+pub fn save_world(world: &GameWorld) -> Result<PathBuf, String> {
+    let path = save_path()?;
+    save_world_to(world, &path)?;
+    Ok(path)
+}
+
+/// Load the world saved with [save_world]
+pub fn load_world() -> Result<GameWorld, String> {
+    load_world_from(&save_path()?)
+}
+
+/// Save the complete state of the given world, including the oxygen content, element data,
+/// gravity fields and rigid bodies, to the given file
+fn save_world_to(world: &GameWorld, path: &Path) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, world.to_string()).map_err(|e| e.to_string())
+}
+
+/// Load a world saved with [save_world_to]
+fn load_world_from(path: &Path) -> Result<GameWorld, String> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("{}: {}", path.display(), e))?
+        .parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::{Concentration, Element};
+    use crate::savegame::{load_world_from, save_world_to};
+    use crate::shapes::ShapeKind;
+    use crate::world::GameWorld;
+    use crate::world::brush::BrushSettings;
+    use crate::world::gravity::GravityField;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn test_save_and_load_the_complete_world() {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let path = env::temp_dir()
+            .join(format!("sandbox-saves-{}", std::process::id()))
+            .join("test.world");
+        let mut world = GameWorld::new(16, 16);
+        let settings = BrushSettings::default();
+        let mut brush_rng = XorShiftRng::seed_from_u64(1);
+        let line = ShapeKind::Line { width: 1 };
+        world.draw_shape(
+            &line,
+            &[(2, 15), (13, 15)],
+            Element::Dust,
+            &settings,
+            &mut brush_rng,
+        );
+        world.draw_shape(
+            &line,
+            &[(2, 14), (2, 14)],
+            Element::FireSource,
+            &settings,
+            &mut brush_rng,
+        );
+        world.draw_shape(
+            &line,
+            &[(0, 10), (15, 10)],
+            Element::SaltWater {
+                concentration: Concentration(37),
+            },
+            &settings,
+            &mut brush_rng,
+        );
+        world.draw_shape(
+            &line,
+            &[(5, 3), (9, 3)],
+            Element::Wood,
+            &settings,
+            &mut brush_rng,
+        );
+        world.create_rigid_bodies(5, 3, 9, 3);
+        world.add_gravity_field(GravityField::Zone {
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+            gravity: (0.5, -0.25),
+        });
+        for _ in 0..10 {
+            world.tick(&mut rng);
+        }
+        assert!(
+            world
+                .board()
+                .iter()
+                .flatten()
+                .any(|e| matches!(e, Element::BurningParticle { .. }))
+        );
+        assert!(!world.rigid_bodies().is_empty());
+        save_world_to(&world, &path).unwrap();
+        let mut loaded = load_world_from(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(loaded.to_string(), world.to_string());
+        assert_eq!(loaded.board(), world.board());
+        assert_eq!(loaded.rigid_bodies(), world.rigid_bodies());
+        assert_eq!(loaded.gravity_fields(), world.gravity_fields());
+        // The loaded world must keep simulating without crashing
+        for _ in 0..10 {
+            loaded.tick(&mut rng);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//   sandbox - Shape tools
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use embedded_graphics::Drawable;
+use embedded_graphics::Pixel;
+use embedded_graphics::prelude::{DrawTarget, Point, Primitive};
+use embedded_graphics::primitives::{Ellipse, Line, Polyline, PrimitiveStyle, Rectangle};
+
+/// A shape that is drawn between the points placed with the mouse
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ShapeKind {
+    /// A straight line with the given thickness from the first to the last point
+    Line { width: u32 },
+    /// A rectangle spanned by the first and the last point
+    Rectangle { filled: bool },
+    /// An ellipse inscribed into the rectangle spanned by the first and the last point
+    Ellipse { filled: bool },
+    /// A closed polygon through all points
+    Polygon { filled: bool },
+}
+
+impl ShapeKind {
+    /// Whether this shape is placed point by point instead of by dragging
+    pub const fn is_multi_point(&self) -> bool {
+        matches!(self, ShapeKind::Polygon { .. })
+    }
+    /// Draw this shape through the given points with the given color
+    pub fn draw<D: DrawTarget>(
+        &self,
+        target: &mut D,
+        points: &[Point],
+        color: D::Color,
+    ) -> Result<(), D::Error> {
+        let (Some(&start), Some(&end)) = (points.first(), points.last()) else {
+            return Ok(());
+        };
+        let style = |filled: bool| match filled {
+            true => PrimitiveStyle::with_fill(color),
+            false => PrimitiveStyle::with_stroke(color, 1),
+        };
+        match *self {
+            ShapeKind::Line { width } => Line::new(start, end)
+                .into_styled(PrimitiveStyle::with_stroke(color, width))
+                .draw(target),
+            ShapeKind::Rectangle { filled } => Rectangle::with_corners(start, end)
+                .into_styled(style(filled))
+                .draw(target),
+            ShapeKind::Ellipse { filled } => {
+                let bounds = Rectangle::with_corners(start, end);
+                Ellipse::new(bounds.top_left, bounds.size)
+                    .into_styled(style(filled))
+                    .draw(target)
+            },
+            ShapeKind::Polygon { filled } => {
+                if filled {
+                    target.draw_iter(polygon_fill(points).map(|p| Pixel(p, color)))?;
+                }
+                let closed: Vec<Point> = points.iter().copied().chain([start]).collect();
+                Polyline::new(&closed)
+                    .into_styled(PrimitiveStyle::with_stroke(color, 1))
+                    .draw(target)
+            },
+        }
+    }
+}
+
+/// All points inside the polygon through the given vertices, using the even-odd rule
+pub(crate) fn polygon_fill(vertices: &[Point]) -> impl Iterator<Item = Point> + '_ {
+    let min_y = vertices.iter().map(|p| p.y).min().unwrap_or(0);
+    let max_y = vertices.iter().map(|p| p.y).max().unwrap_or(-1);
+    (min_y..=max_y).flat_map(move |y| {
+        // Intersect the horizontal line through the pixel centers with all edges
+        let scan_y = y as f32 + 0.5;
+        let mut crossings: Vec<f32> = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .filter(|(a, b)| (a.y as f32 + 0.5 > scan_y) != (b.y as f32 + 0.5 > scan_y))
+            .map(|(a, b)| {
+                let t = (scan_y - (a.y as f32 + 0.5)) / (b.y - a.y) as f32;
+                a.x as f32 + 0.5 + t * (b.x - a.x) as f32
+            })
+            .collect();
+        crossings.sort_by(f32::total_cmp);
+        crossings
+            .chunks_exact(2)
+            .flat_map(|span| (span[0].round() as i32)..(span[1].round() as i32))
+            .map(move |x| Point::new(x, y))
+            .collect::<Vec<_>>()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::shapes::ShapeKind;
+    use crate::world::GameWorld;
+    use crate::world::brush::BrushSettings;
+    use embedded_graphics::prelude::Point;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    /// Draw the given shape with sand into an empty 16x16 world
+    fn draw(shape: ShapeKind, points: &[(i32, i32)]) -> GameWorld {
+        let mut board = GameWorld::new(16, 16);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.draw_shape(
+            &shape,
+            points,
+            Element::Sand,
+            &BrushSettings::default(),
+            &mut rng,
+        );
+        board
+    }
+    /// The number of cells with sand
+    fn count(board: &GameWorld) -> usize {
+        board
+            .board()
+            .iter()
+            .flatten()
+            .filter(|&&e| e == Element::Sand)
+            .count()
+    }
+
+    #[test]
+    fn test_line_has_the_given_thickness() {
+        let board = draw(ShapeKind::Line { width: 3 }, &[(2, 7), (12, 7)]);
+        for y in 6..=8 {
+            assert_eq!(board.board()[7][y], Element::Sand);
+        }
+        assert_eq!(board.board()[7][5], Element::None);
+        assert_eq!(board.board()[7][9], Element::None);
+        assert_eq!(count(&board), 11 * 3);
+    }
+
+    #[test]
+    fn test_outlined_rectangle() {
+        let board = draw(ShapeKind::Rectangle { filled: false }, &[(7, 6), (2, 2)]);
+        assert_eq!(board.board()[2][2], Element::Sand);
+        assert_eq!(board.board()[7][6], Element::Sand);
+        assert_eq!(board.board()[4][2], Element::Sand);
+        assert_eq!(board.board()[2][4], Element::Sand);
+        assert_eq!(board.board()[4][4], Element::None);
+        assert_eq!(count(&board), 2 * 6 + 2 * 3);
+    }
+
+    #[test]
+    fn test_filled_rectangle() {
+        let board = draw(ShapeKind::Rectangle { filled: true }, &[(2, 2), (7, 6)]);
+        assert_eq!(board.board()[4][4], Element::Sand);
+        assert_eq!(board.board()[8][4], Element::None);
+        assert_eq!(count(&board), 6 * 5);
+    }
+
+    #[test]
+    fn test_ellipse_is_inscribed_into_the_rectangle() {
+        let outline = draw(ShapeKind::Ellipse { filled: false }, &[(2, 4), (12, 10)]);
+        let filled = draw(ShapeKind::Ellipse { filled: true }, &[(2, 4), (12, 10)]);
+        for board in [&outline, &filled] {
+            // The ellipse touches the middle of all sides but not the corners
+            assert_eq!(board.board()[7][4], Element::Sand);
+            assert_eq!(board.board()[7][10], Element::Sand);
+            assert_eq!(board.board()[2][7], Element::Sand);
+            assert_eq!(board.board()[12][7], Element::Sand);
+            assert_eq!(board.board()[2][4], Element::None);
+            assert_eq!(board.board()[12][10], Element::None);
+        }
+        assert_eq!(outline.board()[7][7], Element::None);
+        assert_eq!(filled.board()[7][7], Element::Sand);
+    }
+
+    #[test]
+    fn test_polygon_outline_is_closed() {
+        let board = draw(
+            ShapeKind::Polygon { filled: false },
+            &[(1, 1), (8, 1), (1, 8)],
+        );
+        assert_eq!(board.board()[5][1], Element::Sand);
+        assert_eq!(board.board()[1][5], Element::Sand);
+        // The last vertex is connected to the first one
+        assert_eq!(board.board()[4][5], Element::Sand);
+        assert_eq!(board.board()[3][3], Element::None);
+    }
+
+    #[test]
+    fn test_shapes_respect_the_brush_settings() {
+        let mut board = GameWorld::new(16, 16);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let dot = ShapeKind::Line { width: 1 };
+        let settings = BrushSettings::default();
+        board.draw_shape(&dot, &[(3, 3), (3, 3)], Element::Stone, &settings, &mut rng);
+        board.draw_shape(&dot, &[(4, 3), (4, 3)], Element::Water, &settings, &mut rng);
+        let rectangle = ShapeKind::Rectangle { filled: true };
+        let only_empty = BrushSettings {
+            only_empty: true,
+            ..BrushSettings::default()
+        };
+        board.draw_shape(
+            &rectangle,
+            &[(2, 2), (5, 5)],
+            Element::Sand,
+            &only_empty,
+            &mut rng,
+        );
+        assert_eq!(board.board()[3][3], Element::Stone);
+        assert_eq!(board.board()[4][3], Element::Water);
+        assert_eq!(board.board()[5][5], Element::Sand);
+        let protect_solids = BrushSettings {
+            protect_solids: true,
+            ..BrushSettings::default()
+        };
+        board.draw_shape(
+            &rectangle,
+            &[(2, 2), (5, 5)],
+            Element::Wood,
+            &protect_solids,
+            &mut rng,
+        );
+        assert_eq!(board.board()[3][3], Element::Stone);
+        assert_eq!(board.board()[4][3], Element::Wood);
+    }
+
+    #[test]
+    fn test_filled_polygon_covers_interior() {
+        let mut board = GameWorld::new(10, 10);
+        let triangle = [Point::new(1, 1), Point::new(8, 1), Point::new(1, 8)];
+        ShapeKind::Polygon { filled: true }
+            .draw(&mut board, &triangle, Element::Stone)
+            .unwrap();
+        assert_eq!(board.board()[3][3], Element::Stone);
+        assert_eq!(board.board()[1][8], Element::Stone);
+        assert_eq!(board.board()[8][8], Element::None);
+        ShapeKind::Polygon { filled: false }
+            .draw(&mut board, &triangle, Element::Wood)
+            .unwrap();
+        assert_eq!(board.board()[3][3], Element::Stone);
+        assert_eq!(board.board()[1][5], Element::Wood);
+    }
+}
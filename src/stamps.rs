@@ -0,0 +1,148 @@
+//   sandbox - Bitmap brush stamps
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use embedded_graphics::prelude::Point;
+use std::fmt::{Display, Formatter};
+use std::sync::LazyLock;
+use strum_macros::EnumIter;
+
+/// A monochrome bitmap that is used as brush shape
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    /// All pixels, row by row. Set pixels are painted.
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// Parse a plain portable bitmap (PBM with the magic number P1)
+    pub fn from_pbm(data: &str) -> Result<Self, String> {
+        // Comments start with '#' and end at the end of the line
+        let mut tokens = data
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default())
+            .flat_map(str::split_whitespace);
+        if tokens.next() != Some("P1") {
+            return Err("Not a plain PBM bitmap".to_string());
+        }
+        let mut dimension = || {
+            tokens
+                .next()
+                .and_then(|t| t.parse::<usize>().ok())
+                .ok_or_else(|| "Invalid bitmap size".to_string())
+        };
+        let width = dimension()?;
+        let height = dimension()?;
+        // Pixels do not need to be separated by whitespace
+        let pixels: Vec<bool> = tokens
+            .flat_map(str::chars)
+            .map(|c| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                c => Err(format!("Invalid bitmap pixel '{}'", c)),
+            })
+            .collect::<Result<_, _>>()?;
+        if pixels.len() != width * height {
+            return Err(format!(
+                "Expected {} pixels, got {}",
+                width * height,
+                pixels.len()
+            ));
+        }
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+    /// The offsets of all set pixels from the bitmap center,
+    /// with the bitmap scaled so that its larger side is the given number of cells long
+    pub fn scaled_points(&self, size: u32) -> impl Iterator<Item = Point> + '_ {
+        let scale = size as f32 / self.width.max(self.height) as f32;
+        let scaled_width = ((self.width as f32 * scale).round() as usize).max(1);
+        let scaled_height = ((self.height as f32 * scale).round() as usize).max(1);
+        (0..scaled_height)
+            .flat_map(move |y| (0..scaled_width).map(move |x| (x, y)))
+            .filter(move |&(x, y)| {
+                let source_x = x * self.width / scaled_width;
+                let source_y = y * self.height / scaled_height;
+                self.pixels[source_y * self.width + source_x]
+            })
+            .map(move |(x, y)| {
+                Point::new(
+                    x as i32 - scaled_width as i32 / 2,
+                    y as i32 - scaled_height as i32 / 2,
+                )
+            })
+    }
+}
+
+static STAR: LazyLock<Bitmap> =
+    LazyLock::new(|| Bitmap::from_pbm(include_str!("stamps/star.pbm")).unwrap());
+static TRIANGLE: LazyLock<Bitmap> =
+    LazyLock::new(|| Bitmap::from_pbm(include_str!("stamps/triangle.pbm")).unwrap());
+static TEXT: LazyLock<Bitmap> =
+    LazyLock::new(|| Bitmap::from_pbm(include_str!("stamps/text.pbm")).unwrap());
+
+/// The built-in bitmap brush shapes
+#[derive(Eq, PartialEq, Copy, Clone, Debug, EnumIter)]
+pub enum StampKind {
+    Star,
+    Triangle,
+    Text,
+}
+
+impl StampKind {
+    pub fn bitmap(&self) -> &'static Bitmap {
+        match self {
+            StampKind::Star => &STAR,
+            StampKind::Triangle => &TRIANGLE,
+            StampKind::Text => &TEXT,
+        }
+    }
+}
+
+impl Display for StampKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                StampKind::Star => "Star",
+                StampKind::Triangle => "Triangle",
+                StampKind::Text => "Text",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stamps::{Bitmap, StampKind};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_parse_and_scale_pbm() {
+        let bitmap = Bitmap::from_pbm("P1\n# A diagonal\n2 2\n1 0\n01\n").unwrap();
+        assert_eq!(bitmap.scaled_points(2).count(), 2);
+        assert_eq!(bitmap.scaled_points(4).count(), 8);
+        assert!(Bitmap::from_pbm("P1\n2 2\n1 0 1\n").is_err());
+        for stamp in StampKind::iter() {
+            assert!(stamp.bitmap().scaled_points(10).count() > 0);
+        }
+    }
+}
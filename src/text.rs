@@ -0,0 +1,144 @@
+//   sandbox - Text tool
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use embedded_graphics::Drawable;
+use embedded_graphics::Pixel;
+use embedded_graphics::mono_font::ascii::{FONT_4X6, FONT_5X8, FONT_6X10, FONT_7X14, FONT_10X20};
+use embedded_graphics::mono_font::{MonoFont, MonoTextStyle};
+use embedded_graphics::prelude::{Dimensions, DrawTarget, Point, Primitive, Size};
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+
+/// The bitmap fonts of the text tool, from the smallest to the largest
+const FONTS: [&MonoFont; 5] = [&FONT_4X6, &FONT_5X8, &FONT_6X10, &FONT_7X14, &FONT_10X20];
+
+/// Draw the given text centered around the given point, with a character height of about
+/// the given number of cells. The largest font that fits into the height is used.
+/// Heights above the largest font enlarge it by whole multiples.
+pub fn draw_text<D: DrawTarget>(
+    target: &mut D,
+    text: &str,
+    center: Point,
+    height: u32,
+    color: D::Color,
+) -> Result<(), D::Error> {
+    let font = FONTS
+        .iter()
+        .rev()
+        .find(|font| font.character_size.height <= height)
+        .unwrap_or(&FONTS[0]);
+    let scale = (height / font.character_size.height).max(1);
+    let style = TextStyleBuilder::new()
+        .alignment(Alignment::Center)
+        .baseline(Baseline::Middle)
+        .build();
+    // Draw the text at its original size around the origin, the scaled target moves it to the center
+    Text::with_text_style(text, Point::zero(), MonoTextStyle::new(font, color), style).draw(
+        &mut ScaledTarget {
+            target,
+            offset: center,
+            scale,
+        },
+    )?;
+    Ok(())
+}
+
+/// A draw target that enlarges each pixel to a square of the given size and moves it by the given offset
+struct ScaledTarget<'a, D> {
+    target: &'a mut D,
+    offset: Point,
+    scale: u32,
+}
+
+impl<D: DrawTarget> Dimensions for ScaledTarget<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        let bounds = self.target.bounding_box();
+        Rectangle::new(
+            (bounds.top_left - self.offset) / self.scale as i32,
+            Size::new(
+                bounds.size.width.div_ceil(self.scale) + 1,
+                bounds.size.height.div_ceil(self.scale) + 1,
+            ),
+        )
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for ScaledTarget<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if self.scale == 1 {
+                self.target.draw_iter([Pixel(point + self.offset, color)])?;
+            } else {
+                Rectangle::new(
+                    point * self.scale as i32 + self.offset,
+                    Size::new(self.scale, self.scale),
+                )
+                .into_styled(PrimitiveStyle::with_fill(color))
+                .draw(self.target)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::text::draw_text;
+    use crate::world::GameWorld;
+    use embedded_graphics::prelude::Point;
+
+    /// Draw the given text into the center of an empty world and return all cells it covers
+    fn draw_centered(text: &str, height: u32) -> Vec<(usize, usize)> {
+        let mut board = GameWorld::new(100, 60);
+        draw_text(
+            &mut board,
+            text,
+            Point::new(50, 30),
+            height,
+            Element::BrickWall,
+        )
+        .unwrap();
+        (0..100)
+            .flat_map(|x| (0..60).map(move |y| (x, y)))
+            .filter(|&(x, y)| board.board()[x][y] == Element::BrickWall)
+            .collect()
+    }
+    #[test]
+    fn test_text_is_centered() {
+        let cells = draw_centered("I", 10);
+        let min_y = cells.iter().map(|c| c.1).min().unwrap();
+        let max_y = cells.iter().map(|c| c.1).max().unwrap();
+        let min_x = cells.iter().map(|c| c.0).min().unwrap();
+        let max_x = cells.iter().map(|c| c.0).max().unwrap();
+        assert!(min_y < 30 && max_y > 30);
+        assert!(min_x <= 50 && max_x >= 50);
+    }
+    #[test]
+    fn test_text_is_scaled_by_whole_multiples() {
+        // Twice the largest font height draws every pixel as a 2x2 square
+        assert_eq!(
+            draw_centered("I", 40).len(),
+            draw_centered("I", 20).len() * 4
+        );
+    }
+}
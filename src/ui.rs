@@ -14,24 +14,50 @@
 //   You should have received a copy of the GNU General Public License
 //   along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::canvas_display::CanvasDisplay;
-use crate::colors::{BOARD_BORDER_COLOR, WINDOW_BACKGROUND_COLOR};
+use crate::canvas_display::{BoardPreview, CanvasDisplay};
+use crate::colors::{
+    ATTRACTOR_COLOR, BOARD_BORDER_COLOR, GRAVITY_ZONE_COLOR, REPULSOR_COLOR, SELECTION_COLOR,
+    WINDOW_BACKGROUND_COLOR,
+};
+use crate::element::Element;
+use crate::shapes::ShapeKind;
+use crate::stamps::StampKind;
+use crate::text::draw_text;
 use crate::world::GameWorld;
-use embedded_graphics::Drawable;
+use crate::world::brush::{BrushSettings, PenStroke};
+use crate::world::gravity::{AttractorKind, GravityField, HOLE_CORE_RADIUS};
+use crate::world::snippet::{Selection, Snippet};
 use embedded_graphics::geometry::Size;
+use embedded_graphics::prelude::DrawTarget;
 use embedded_graphics::prelude::Primitive;
-use embedded_graphics::primitives::{Circle, PrimitiveStyle, Rectangle};
+use embedded_graphics::primitives::{Circle, Line, PrimitiveStyle, Rectangle};
+use embedded_graphics::{Drawable, Pixel};
 use sdl2::pixels::Color;
 use sdl2::rect::{Point, Rect};
 use sdl2::render::{Canvas, RenderTarget, Texture, WindowCanvas};
 
 pub const CURSOR_PREVIEW_COLOR: Color = Color::RGBA(0xff, 0xff, 0xff, 0x30);
+/// The opacity of the element colors in the paste preview
+const PASTE_PREVIEW_ALPHA: u8 = 0xa0;
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub enum CursorKind {
     Square { size: u32 },
     Circle { size: u32 },
     Pen { size: u32 },
+    GravityZone,
+    Attractor { radius: u32 },
+    RigidBody,
+    Shape { shape: ShapeKind },
+    Fill { diagonal: bool },
+    Replace { size: u32 },
+    Stamp { stamp: StampKind, size: u32 },
+    Select { lasso: bool },
+    Paste,
+    Text { size: u32 },
 }
+
+/// The maximum size of the square, circle, pen, replace, stamp and text brushes
+pub const MAX_BRUSH_SIZE: u32 = 200;
 pub struct Ui {
     pub win_width: usize,
     pub win_height: usize,
@@ -41,14 +67,138 @@ pub struct Ui {
     pub board_height: usize,
     cursor: CursorKind,
     pub(crate) scaling_factor: usize,
+    /// The gravity vector of newly placed gravity zones
+    pub(crate) zone_gravity: (f32, f32),
+    /// The kind of newly placed attractors
+    pub(crate) attractor_kind: AttractorKind,
+    /// The strength of newly placed attractors
+    pub(crate) attractor_strength: f32,
+    /// Whether the gravity field overlay should be drawn
+    pub(crate) show_gravity_fields: bool,
+    /// The modifiers of the square, circle and pen brushes
+    pub(crate) brush: BrushSettings,
+    /// The element that is painted over by the replace brush
+    pub(crate) replace_source: Element,
+    /// Whether the reaction encyclopedia window is open
+    pub(crate) show_reactions: bool,
+    /// The text written by the text tool
+    pub(crate) text: String,
+    /// The selected area of the board
+    pub(crate) selection: Option<Selection>,
+    /// The copied cells that are placed by the paste tool
+    pub(crate) clipboard: Option<Snippet>,
+    /// Whether the stamp library window is open
+    pub(crate) show_library: bool,
+    /// The name under which the clipboard is saved to the stamp library
+    pub(crate) stamp_name: String,
+    /// The error of the last stamp library operation, if it failed
+    pub(crate) library_error: Option<String>,
+    /// Whether the simulation is paused
+    pub(crate) paused: bool,
+    /// Whether a single tick should be simulated in the next frame while paused
+    pub(crate) step: bool,
+    /// Whether the keyboard shortcut overlay is open
+    pub(crate) show_help: bool,
+    /// The query of the element search window, if the window is open
+    pub(crate) element_search: Option<String>,
+    /// The message shown in the status bar, e.g., the result of saving the world
+    pub(crate) status_message: Option<String>,
+    /// The width and height of the world created by the new world dialog, if the dialog is open
+    pub(crate) new_world_size: Option<(usize, usize)>,
+    /// The board coordinate shown in the top left corner of the board area
+    camera: (f32, f32),
+    /// The size of the world in board cells
+    world_size: (usize, usize),
 }
 
+/// The available zoom levels, i.e., the size of one board cell in pixels
+pub const ZOOM_LEVELS: [usize; 8] = [1, 2, 3, 4, 6, 8, 12, 16];
+
 impl Ui {
-    pub(crate) fn rescale(&mut self, board: &mut GameWorld, new_scale: &usize) {
-        self.scaling_factor = *new_scale;
-        board.resize(
-            self.board_width / self.scaling_factor,
-            self.board_height / self.scaling_factor,
+    /// Set the zoom level, keeping the center of the board area in place
+    pub(crate) fn rescale(&mut self, new_scale: &usize) {
+        let center = (
+            self.left_padding() + self.board_width as i32 / 2,
+            self.top_padding() + self.board_height as i32 / 2,
+        );
+        self.zoom_to(*new_scale, center);
+    }
+    /// Zoom in (positive steps) or out (negative steps) by the given number of zoom levels,
+    /// keeping the center of the board area in place
+    pub(crate) fn zoom(&mut self, steps: i32) {
+        self.zoom_at(
+            self.left_padding() + self.board_width as i32 / 2,
+            self.top_padding() + self.board_height as i32 / 2,
+            steps,
+        );
+    }
+    /// Zoom in (positive steps) or out (negative steps) by the given number of zoom levels,
+    /// keeping the board cell below the given window coordinate in place
+    pub(crate) fn zoom_at(&mut self, window_x: i32, window_y: i32, steps: i32) {
+        let current = ZOOM_LEVELS
+            .iter()
+            .position(|&z| z >= self.scaling_factor)
+            .unwrap_or(ZOOM_LEVELS.len() - 1);
+        let new = (current as i32 + steps).clamp(0, ZOOM_LEVELS.len() as i32 - 1);
+        self.zoom_to(ZOOM_LEVELS[new as usize], (window_x, window_y));
+    }
+    fn zoom_to(&mut self, new_scale: usize, anchor: (i32, i32)) {
+        let offset = (
+            (anchor.0 - self.left_padding()) as f32,
+            (anchor.1 - self.top_padding()) as f32,
+        );
+        let old_scale = self.scaling_factor as f32;
+        self.camera.0 += offset.0 / old_scale - offset.0 / new_scale as f32;
+        self.camera.1 += offset.1 / old_scale - offset.1 / new_scale as f32;
+        self.scaling_factor = new_scale;
+        self.clamp_camera();
+    }
+    /// Show a world of the given size, centering the camera on it
+    pub(crate) fn set_world_size(&mut self, world_size: (usize, usize)) {
+        self.world_size = world_size;
+        self.center_camera_on(world_size.0 as f32 / 2.0, world_size.1 as f32 / 2.0);
+    }
+    /// The size of the world in board cells
+    pub(crate) const fn world_size(&self) -> (usize, usize) {
+        self.world_size
+    }
+    /// Move the camera by the given distance in window pixels, i.e., drag the board along with the mouse
+    pub(crate) fn pan(&mut self, dx: i32, dy: i32) {
+        self.camera.0 -= dx as f32 / self.scaling_factor as f32;
+        self.camera.1 -= dy as f32 / self.scaling_factor as f32;
+        self.clamp_camera();
+    }
+    /// Move the camera so that the given board coordinate is in the center of the board area
+    pub(crate) fn center_camera_on(&mut self, x: f32, y: f32) {
+        let (visible_width, visible_height) = self.visible_cells();
+        self.camera = (x - visible_width / 2.0, y - visible_height / 2.0);
+        self.clamp_camera();
+    }
+    /// The visible part of the world as (x, y, width, height) in board cells
+    pub(crate) fn visible_area(&self) -> (f32, f32, f32, f32) {
+        let (visible_width, visible_height) = self.visible_cells();
+        (self.camera.0, self.camera.1, visible_width, visible_height)
+    }
+    /// The number of board cells that fit into the board area, including partially visible cells
+    fn visible_cells(&self) -> (f32, f32) {
+        (
+            self.board_width as f32 / self.scaling_factor as f32,
+            self.board_height as f32 / self.scaling_factor as f32,
+        )
+    }
+    /// Keep the camera inside the world. Worlds smaller than the board area are centered.
+    fn clamp_camera(&mut self) {
+        let (visible_width, visible_height) = self.visible_cells();
+        let clamp = |pos: f32, visible: f32, size: usize| {
+            if visible >= size as f32 {
+                (size as f32 - visible) / 2.0
+            } else {
+                pos.clamp(0.0, size as f32 - visible)
+            }
+        };
+        self.camera = (
+            clamp(self.camera.0, visible_width, self.world_size.0),
+            clamp(self.camera.1, visible_height, self.world_size.1),
         );
     }
 }
@@ -75,67 +225,252 @@ impl Ui {
         canvas: &mut Canvas<T>,
         x: i32,
         y: i32,
+        pen: &PenStroke,
     ) -> Result<(), String> {
         if let Some((x, y)) = self.window_to_board_coordinate(x, y) {
-            let mut canvas_display = CanvasDisplay {
-                canvas,
-                width: self.win_width,
-                height: self.win_height,
-                left_padding: self.left_padding(),
-                right_padding: self.right_padding(),
-                top_padding: self.top_padding(),
-                bottom_padding: self.bottom_padding(),
-            };
-            // TODO Rewrite Canvas Display to use scaling factor to show pixel-perfect previews
-            // The X coord of the preview tile center
-            let draw_x = x * self.scaling_factor as i32
-                + self.left_padding()
-                + (self.scaling_factor / 2) as i32;
-            // The X coord of the preview tile center
-            let draw_y = y * self.scaling_factor as i32
-                + self.top_padding()
-                + (self.scaling_factor / 2) as i32;
+            let center = embedded_graphics::prelude::Point::new(x, y);
             match self.cursor {
-                CursorKind::Square { size } => {
-                    Rectangle::with_center(
-                        embedded_graphics::prelude::Point::new(draw_x, draw_y),
-                        Size::new(
-                            size * self.scaling_factor as u32,
-                            size * self.scaling_factor as u32,
+                CursorKind::Attractor { radius } => {
+                    // The attractor radius is shown as a thin outline around the preview tile center
+                    let (draw_x, draw_y) = self.board_to_window_coordinate(x, y);
+                    Circle::with_center(
+                        embedded_graphics::prelude::Point::new(
+                            draw_x + (self.scaling_factor / 2) as i32,
+                            draw_y + (self.scaling_factor / 2) as i32,
                         ),
+                        radius * 2 * self.scaling_factor as u32,
                     )
-                    .into_styled(PrimitiveStyle::with_fill(CURSOR_PREVIEW_COLOR.into()))
-                    .draw(&mut canvas_display)?;
+                    .into_styled(PrimitiveStyle::with_stroke(CURSOR_PREVIEW_COLOR.into(), 1))
+                    .draw(&mut self.canvas_display(canvas))?;
                 },
-                CursorKind::Pen { size, .. } => {
-                    Rectangle::with_center(
-                        embedded_graphics::prelude::Point::new(draw_x, draw_y),
-                        Size::new(
-                            size * self.scaling_factor as u32,
-                            size * self.scaling_factor as u32,
-                        ),
-                    )
-                    .into_styled(PrimitiveStyle::with_fill(CURSOR_PREVIEW_COLOR.into()))
-                    .draw(&mut canvas_display)?;
+                CursorKind::Text { size } => draw_text(
+                    &mut self.board_preview(canvas),
+                    &self.text,
+                    center,
+                    size,
+                    CURSOR_PREVIEW_COLOR.into(),
+                )?,
+                // The pen shows the cells of the round cap at the current stroke width
+                CursorKind::Pen { size } => {
+                    self.cursor.with_size(pen.preview_width(size)).draw_brush(
+                        &mut self.board_preview(canvas),
+                        center,
+                        CURSOR_PREVIEW_COLOR.into(),
+                    )?
                 },
-                CursorKind::Circle { size } => {
-                    Circle::with_center(
-                        embedded_graphics::prelude::Point::new(draw_x, draw_y),
-                        size * self.scaling_factor as u32,
+                // All other cursors show exactly the cells they paint
+                _ => self.cursor.draw_brush(
+                    &mut self.board_preview(canvas),
+                    center,
+                    CURSOR_PREVIEW_COLOR.into(),
+                )?,
+            }
+        }
+        Ok(())
+    }
+    /// Draw the preview of a rectangular area that is being dragged between the given window coordinates
+    pub(crate) fn draw_area_preview<T: RenderTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        start: (i32, i32),
+        end: (i32, i32),
+    ) -> Result<(), String> {
+        if let Some((x1, y1)) = self.window_to_board_coordinate(start.0, start.1)
+            && let Some((x2, y2)) = self.window_to_board_coordinate(end.0, end.1)
+        {
+            let mut canvas_display = self.canvas_display(canvas);
+            let (wx, wy) = self.board_to_window_coordinate(x1.min(x2), y1.min(y2));
+            Rectangle::new(
+                embedded_graphics::prelude::Point::new(wx, wy),
+                Size::new(
+                    (x1.abs_diff(x2) + 1) * self.scaling_factor as u32,
+                    (y1.abs_diff(y2) + 1) * self.scaling_factor as u32,
+                ),
+            )
+            .into_styled(PrimitiveStyle::with_fill(CURSOR_PREVIEW_COLOR.into()))
+            .draw(&mut canvas_display)?;
+        }
+        Ok(())
+    }
+    /// Draw a pixel-exact preview of the given shape through the given board coordinates
+    pub(crate) fn draw_shape_preview<T: RenderTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        shape: &ShapeKind,
+        points: &[(i32, i32)],
+    ) -> Result<(), String> {
+        let points: Vec<embedded_graphics::prelude::Point> = points
+            .iter()
+            .map(|&(x, y)| embedded_graphics::prelude::Point::new(x, y))
+            .collect();
+        shape.draw(
+            &mut self.board_preview(canvas),
+            &points,
+            CURSOR_PREVIEW_COLOR.into(),
+        )
+    }
+    /// Draw an overlay over all selected cells
+    pub(crate) fn draw_selection<T: RenderTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+    ) -> Result<(), String> {
+        if let Some(selection) = &self.selection {
+            self.board_preview(canvas).draw_iter(
+                selection
+                    .cells()
+                    .into_iter()
+                    .map(|p| Pixel(p, SELECTION_COLOR.into())),
+            )?;
+        }
+        Ok(())
+    }
+    /// Draw a preview of the clipboard pasted centered around the given window coordinate
+    pub(crate) fn draw_paste_preview<T: RenderTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        x: i32,
+        y: i32,
+    ) -> Result<(), String> {
+        if let Some(snippet) = &self.clipboard
+            && let Some((x, y)) = self.window_to_board_coordinate(x, y)
+        {
+            self.board_preview(canvas).draw_iter(
+                snippet
+                    .pixels_at(embedded_graphics::prelude::Point::new(x, y))
+                    .map(|Pixel(p, element)| {
+                        let color = element.color();
+                        Pixel(
+                            p,
+                            Color::RGBA(color.r, color.g, color.b, PASTE_PREVIEW_ALPHA).into(),
+                        )
+                    }),
+            )?;
+        }
+        Ok(())
+    }
+    /// Draw an overlay showing all gravity fields of the given world
+    pub(crate) fn draw_gravity_fields<T: RenderTarget>(
+        &self,
+        canvas: &mut Canvas<T>,
+        world: &GameWorld,
+    ) -> Result<(), String> {
+        if !self.show_gravity_fields {
+            return Ok(());
+        }
+        let scf = self.scaling_factor as i32;
+        let mut canvas_display = self.canvas_display(canvas);
+        for field in world.gravity_fields() {
+            match field {
+                GravityField::Zone {
+                    x,
+                    y,
+                    width,
+                    height,
+                    gravity,
+                } => {
+                    let (wx, wy) = self.board_to_window_coordinate(*x as i32, *y as i32);
+                    let size = Size::new(
+                        (width * scf as usize) as u32,
+                        (height * scf as usize) as u32,
+                    );
+                    Rectangle::new(embedded_graphics::prelude::Point::new(wx, wy), size)
+                        .into_styled(PrimitiveStyle::with_stroke(GRAVITY_ZONE_COLOR.into(), 1))
+                        .draw(&mut canvas_display)?;
+                    // Show the gravity direction as a line from the zone center
+                    let center = embedded_graphics::prelude::Point::new(
+                        wx + size.width as i32 / 2,
+                        wy + size.height as i32 / 2,
+                    );
+                    let arrow_len =
+                        (size.width.min(size.height) as f32 / 3.0).min(8.0 * scf as f32);
+                    Line::new(
+                        center,
+                        center
+                            + embedded_graphics::prelude::Point::new(
+                                (gravity.0 * arrow_len) as i32,
+                                (gravity.1 * arrow_len) as i32,
+                            ),
                     )
-                    .into_styled(PrimitiveStyle::with_fill(CURSOR_PREVIEW_COLOR.into()))
+                    .into_styled(PrimitiveStyle::with_stroke(GRAVITY_ZONE_COLOR.into(), 2))
                     .draw(&mut canvas_display)?;
                 },
+                GravityField::Attractor {
+                    x, y, radius, kind, ..
+                } => {
+                    let (wx, wy) = self.board_to_window_coordinate(*x as i32, *y as i32);
+                    let center = embedded_graphics::prelude::Point::new(wx + scf / 2, wy + scf / 2);
+                    let color = if kind.attracts() {
+                        ATTRACTOR_COLOR
+                    } else {
+                        REPULSOR_COLOR
+                    };
+                    Circle::with_center(center, (radius * 2) as u32 * scf as u32)
+                        .into_styled(PrimitiveStyle::with_stroke(color.into(), 1))
+                        .draw(&mut canvas_display)?;
+                    if matches!(kind, AttractorKind::BlackHole | AttractorKind::WhiteHole) {
+                        Circle::with_center(center, (HOLE_CORE_RADIUS * 2.0) as u32 * scf as u32)
+                            .into_styled(PrimitiveStyle::with_fill(color.into()))
+                            .draw(&mut canvas_display)?;
+                    }
+                },
             }
         }
         Ok(())
     }
+    /// A draw target that draws board cells at their scaled position in the window
+    fn board_preview<'a, T: RenderTarget>(
+        &'a self,
+        canvas: &'a mut Canvas<T>,
+    ) -> BoardPreview<'a, 'a, T> {
+        BoardPreview {
+            display: self.canvas_display(canvas),
+            ui: self,
+        }
+    }
+    fn canvas_display<'a, T: RenderTarget>(
+        &self,
+        canvas: &'a mut Canvas<T>,
+    ) -> CanvasDisplay<'a, T> {
+        CanvasDisplay {
+            canvas,
+            width: self.win_width,
+            height: self.win_height,
+            left_padding: self.left_padding(),
+            right_padding: self.right_padding(),
+            top_padding: self.top_padding(),
+            bottom_padding: self.bottom_padding(),
+        }
+    }
 }
 
 impl Ui {
     pub(crate) fn set_cursor(&mut self, cursor: CursorKind) {
         self.cursor = cursor
     }
+    /// Select the next (positive steps) or previous (negative steps) cursor of the cursor bar.
+    /// Resized brushes continue from the first cursor of their category.
+    pub(crate) fn cycle_cursor(&mut self, steps: i32) {
+        let cursors = CursorKind::ui_cursors();
+        let current = cursors
+            .iter()
+            .position(|c| *c == self.cursor)
+            .or_else(|| {
+                cursors
+                    .iter()
+                    .position(|c| c.category_text() == self.cursor.category_text())
+            })
+            .unwrap_or(0);
+        let next = (current as i32 + steps).rem_euclid(cursors.len() as i32);
+        self.cursor = cursors[next as usize];
+    }
+    /// Grow (positive steps) or shrink (negative steps) the current brush by about 10% per step
+    pub(crate) fn resize_cursor(&mut self, steps: i32) {
+        if let Some(size) = self.cursor.size() {
+            let step = (size / 10).max(1) as i32;
+            let new_size = (size as i32 + steps * step).clamp(1, MAX_BRUSH_SIZE as i32);
+            self.cursor = self.cursor.with_size(new_size as u32);
+        }
+    }
 }
 
 const HORIZ_MARGIN: usize = 240;
@@ -145,23 +480,41 @@ impl Ui {
     pub fn cursor(&self) -> &CursorKind {
         &self.cursor
     }
+    /// The window coordinate of the top left corner of the given board tile
+    pub(crate) fn board_to_window_coordinate(&self, x: i32, y: i32) -> (i32, i32) {
+        let scf = self.scaling_factor as f32;
+        (
+            ((x as f32 - self.camera.0) * scf).ceil() as i32 + self.left_padding(),
+            ((y as f32 - self.camera.1) * scf).ceil() as i32 + self.top_padding(),
+        )
+    }
+    /// Whether the given window coordinate is inside the board area
+    pub(crate) const fn is_over_board(&self, window_x: i32, window_y: i32) -> bool {
+        window_x >= self.left_padding()
+            && window_y >= self.top_padding()
+            && window_x < self.left_padding() + self.board_width as i32
+            && window_y < self.top_padding() + self.board_height as i32
+    }
     pub(crate) fn window_to_board_coordinate(
         &self,
         window_x: i32,
         window_y: i32,
     ) -> Option<(i32, i32)> {
+        if !self.is_over_board(window_x, window_y) {
+            return None;
+        }
+        let scf = self.scaling_factor as f32;
         let ret = (
-            (window_x - self.left_padding()) / self.scaling_factor as i32,
-            (window_y - self.top_padding()) / self.scaling_factor as i32,
+            (self.camera.0 + (window_x - self.left_padding()) as f32 / scf).floor() as i32,
+            (self.camera.1 + (window_y - self.top_padding()) as f32 / scf).floor() as i32,
         );
         if ret.0 < 0
             || ret.1 < 0
-            || ret.0 * self.scaling_factor as i32 >= self.board_width as i32
-            || ret.1 * self.scaling_factor as i32 >= self.board_height as i32
+            || ret.0 >= self.world_size.0 as i32
+            || ret.1 >= self.world_size.1 as i32
         {
             None
         } else {
-            // println!("Board {} {} Cursor {} {}", self.board_width, self.board_height, ret.0, ret.1);
             Some(ret)
         }
     }
@@ -181,7 +534,12 @@ impl Ui {
     const fn bottom_padding(&self) -> i32 {
         self.win_height as i32 - self.top_padding() - self.board_height as i32
     }
-    pub fn new(width: usize, height: usize, scaling_factor: usize) -> Self {
+    pub fn new(
+        width: usize,
+        height: usize,
+        scaling_factor: usize,
+        world_size: (usize, usize),
+    ) -> Self {
         let mut ret = Self {
             win_width: width,
             win_height: height,
@@ -189,27 +547,40 @@ impl Ui {
             board_height: height - VERT_MARGIN,
             cursor: CursorKind::Pen { size: 3 },
             scaling_factor,
+            zone_gravity: (0.0, -1.0),
+            attractor_kind: AttractorKind::default(),
+            attractor_strength: 2.0,
+            show_gravity_fields: true,
+            brush: BrushSettings::default(),
+            replace_source: Element::Water,
+            show_reactions: false,
+            text: "Sandbox".to_string(),
+            selection: None,
+            clipboard: None,
+            show_library: false,
+            stamp_name: String::new(),
+            library_error: None,
+            paused: false,
+            step: false,
+            show_help: false,
+            element_search: None,
+            status_message: None,
+            new_world_size: None,
+            camera: (0.0, 0.0),
+            world_size,
         };
-        ret.board_width = ret.board_width - (ret.board_width % ret.scaling_factor);
-        ret.board_height = ret.board_height - (ret.board_height % ret.scaling_factor);
+        ret.set_world_size(world_size);
         ret
     }
-    pub fn resize(self, board: &mut GameWorld, new_width: usize, new_height: usize) -> Self {
+    pub fn resize(self, new_width: usize, new_height: usize) -> Self {
         let mut ret = Self {
             win_width: new_width,
             win_height: new_height,
             board_width: new_width - HORIZ_MARGIN,
             board_height: new_height - VERT_MARGIN,
-            cursor: self.cursor,
-            scaling_factor: self.scaling_factor,
+            ..self
         };
-        ret.board_width = ret.board_width - (ret.board_width % ret.scaling_factor);
-        ret.board_height = ret.board_height - (ret.board_height % ret.scaling_factor);
-
-        board.resize(
-            ret.board_width / ret.scaling_factor,
-            ret.board_height / ret.scaling_factor,
-        );
+        ret.clamp_camera();
         ret
     }
     /// Draw the window content
@@ -251,25 +622,29 @@ impl Ui {
             Point::from((left_padding, top_padding)),
         )?;
 
-        // Draw the board
+        // Draw the visible part of the board. Cells outside the world are drawn in the background color.
+        let scf = self.scaling_factor as f32;
+        let to_cell = |camera: f32, win: usize, size: usize| {
+            let cell = (camera + win as f32 / scf).floor();
+            (cell >= 0.0 && cell < size as f32).then_some(cell as usize)
+        };
+        let columns: Vec<Option<usize>> = (0..self.board_width)
+            .map(|win_x| to_cell(self.camera.0, win_x, self.world_size.0))
+            .collect();
         texture.with_lock(
             Rect::from((0, 0, self.board_width as u32, self.board_height as u32)),
             |pixel_data, _pitch| {
-                for board_y in 0..self.board_height / self.scaling_factor {
-                    for board_x in 0..self.board_width / self.scaling_factor {
-                        for x_scf in 0..self.scaling_factor {
-                            for y_scf in 0..self.scaling_factor {
-                                let win_x = board_x * self.scaling_factor + x_scf;
-                                let win_y = board_y * self.scaling_factor + y_scf;
-                                pixel_data[((win_y * self.board_width) + win_x) * 4 + 3] = 0xff;
-                                pixel_data[((win_y * self.board_width) + win_x) * 4 + 2] =
-                                    world.board()[board_x][board_y].color().r;
-                                pixel_data[((win_y * self.board_width) + win_x) * 4 + 1] =
-                                    world.board()[board_x][board_y].color().g;
-                                pixel_data[((win_y * self.board_width) + win_x) * 4] =
-                                    world.board()[board_x][board_y].color().b;
-                            }
-                        }
+                for win_y in 0..self.board_height {
+                    let board_y = to_cell(self.camera.1, win_y, self.world_size.1);
+                    for (win_x, board_x) in columns.iter().enumerate() {
+                        let color = match (board_x, board_y) {
+                            (Some(board_x), Some(board_y)) => world.color_at(*board_x, board_y),
+                            _ => WINDOW_BACKGROUND_COLOR,
+                        };
+                        pixel_data[((win_y * self.board_width) + win_x) * 4 + 3] = 0xff;
+                        pixel_data[((win_y * self.board_width) + win_x) * 4 + 2] = color.r;
+                        pixel_data[((win_y * self.board_width) + win_x) * 4 + 1] = color.g;
+                        pixel_data[((win_y * self.board_width) + win_x) * 4] = color.b;
                     }
                 }
             },
@@ -293,23 +668,67 @@ impl CursorKind {
     pub const fn ui_cursors() -> &'static [Self] {
         &[
             CursorKind::Square { size: 1 },
-            CursorKind::Square { size: 2 },
             CursorKind::Square { size: 3 },
-            CursorKind::Square { size: 5 },
             CursorKind::Square { size: 10 },
-            CursorKind::Square { size: 15 },
             CursorKind::Square { size: 25 },
-            CursorKind::Square { size: 50 },
             CursorKind::Circle { size: 5 },
-            CursorKind::Circle { size: 10 },
             CursorKind::Circle { size: 15 },
-            CursorKind::Circle { size: 25 },
             CursorKind::Circle { size: 50 },
             CursorKind::Pen { size: 1 },
-            CursorKind::Pen { size: 2 },
             CursorKind::Pen { size: 3 },
-            CursorKind::Pen { size: 5 },
             CursorKind::Pen { size: 10 },
+            CursorKind::Stamp {
+                stamp: StampKind::Star,
+                size: 15,
+            },
+            CursorKind::Stamp {
+                stamp: StampKind::Triangle,
+                size: 15,
+            },
+            CursorKind::Stamp {
+                stamp: StampKind::Text,
+                size: 23,
+            },
+            CursorKind::GravityZone,
+            CursorKind::Attractor { radius: 15 },
+            CursorKind::Attractor { radius: 30 },
+            CursorKind::Attractor { radius: 60 },
+            CursorKind::RigidBody,
+            CursorKind::Shape {
+                shape: ShapeKind::Line { width: 1 },
+            },
+            CursorKind::Shape {
+                shape: ShapeKind::Line { width: 3 },
+            },
+            CursorKind::Shape {
+                shape: ShapeKind::Rectangle { filled: false },
+            },
+            CursorKind::Shape {
+                shape: ShapeKind::Rectangle { filled: true },
+            },
+            CursorKind::Shape {
+                shape: ShapeKind::Ellipse { filled: false },
+            },
+            CursorKind::Shape {
+                shape: ShapeKind::Ellipse { filled: true },
+            },
+            CursorKind::Shape {
+                shape: ShapeKind::Polygon { filled: false },
+            },
+            CursorKind::Shape {
+                shape: ShapeKind::Polygon { filled: true },
+            },
+            CursorKind::Fill { diagonal: false },
+            CursorKind::Fill { diagonal: true },
+            CursorKind::Replace { size: 5 },
+            CursorKind::Replace { size: 15 },
+            CursorKind::Replace { size: 50 },
+            CursorKind::Select { lasso: false },
+            CursorKind::Select { lasso: true },
+            CursorKind::Paste,
+            CursorKind::Text { size: 6 },
+            CursorKind::Text { size: 10 },
+            CursorKind::Text { size: 20 },
         ]
     }
     /// The text to show on UI buttons for this cursor
@@ -318,6 +737,26 @@ impl CursorKind {
             CursorKind::Square { size } => size.to_string(),
             CursorKind::Circle { size } => size.to_string(),
             CursorKind::Pen { size, .. } => size.to_string(),
+            CursorKind::GravityZone => "Zone".to_string(),
+            CursorKind::Attractor { radius } => radius.to_string(),
+            CursorKind::RigidBody => "Body".to_string(),
+            CursorKind::Shape { shape } => match shape {
+                ShapeKind::Line { width } => width.to_string(),
+                ShapeKind::Rectangle { filled: false } => "□".to_string(),
+                ShapeKind::Rectangle { filled: true } => "■".to_string(),
+                ShapeKind::Ellipse { filled: false } => "○".to_string(),
+                ShapeKind::Ellipse { filled: true } => "●".to_string(),
+                ShapeKind::Polygon { filled: false } => "△".to_string(),
+                ShapeKind::Polygon { filled: true } => "▲".to_string(),
+            },
+            CursorKind::Fill { diagonal: false } => "4".to_string(),
+            CursorKind::Fill { diagonal: true } => "8".to_string(),
+            CursorKind::Replace { size } => size.to_string(),
+            CursorKind::Stamp { stamp, .. } => stamp.to_string(),
+            CursorKind::Select { lasso: false } => "Rect".to_string(),
+            CursorKind::Select { lasso: true } => "Lasso".to_string(),
+            CursorKind::Paste => "Paste".to_string(),
+            CursorKind::Text { size } => size.to_string(),
         }
     }
     /// The text to show on UI tooltips for this cursor
@@ -331,6 +770,57 @@ impl CursorKind {
             CursorKind::Pen { size, .. } => {
                 format!("Continuous pen with a stroke of {} pixels", size)
             },
+            CursorKind::GravityZone => {
+                "Drag a rectangular zone with its own gravity. Right-click to remove.".to_string()
+            },
+            CursorKind::Attractor { radius } => format!(
+                "An attractor with a radius of {} pixels. Right-click to remove.",
+                radius
+            ),
+            CursorKind::RigidBody => {
+                "Drag a rectangle to turn all wood and stone inside it into falling rigid bodies"
+                    .to_string()
+            },
+            CursorKind::Shape { shape } => {
+                let fill = |filled: &bool| if *filled { "filled" } else { "outlined" };
+                match shape {
+                    ShapeKind::Line { width } => {
+                        format!("Drag a straight line with a stroke of {} pixels", width)
+                    },
+                    ShapeKind::Rectangle { filled } => format!("Drag a {} rectangle", fill(filled)),
+                    ShapeKind::Ellipse { filled } => format!("Drag a {} ellipse", fill(filled)),
+                    ShapeKind::Polygon { filled } => format!(
+                        "Click to place the corners of a {} polygon, right-click to draw it",
+                        fill(filled)
+                    ),
+                }
+            },
+            CursorKind::Fill { diagonal: false } => {
+                "Fill the clicked region of one element, connected along edges".to_string()
+            },
+            CursorKind::Fill { diagonal: true } => {
+                "Fill the clicked region of one element, connected along edges and corners"
+                    .to_string()
+            },
+            CursorKind::Replace { size } => format!(
+                "A {}x{} brush that only paints over the element chosen in the left panel",
+                size, size
+            ),
+            CursorKind::Stamp { stamp, size } => {
+                format!("A {} stamp with a size of {} pixels", stamp, size)
+            },
+            CursorKind::Select { lasso: false } => {
+                "Drag to select a rectangle. Right-click to clear the selection.".to_string()
+            },
+            CursorKind::Select { lasso: true } => {
+                "Draw the outline of an area to select it. Right-click to clear the selection."
+                    .to_string()
+            },
+            CursorKind::Paste => "Click to paste the copied cells".to_string(),
+            CursorKind::Text { size } => format!(
+                "Click to write the text from the left panel, {} pixels high",
+                size
+            ),
         }
     }
     /// The category text to show in the UI for this cursor
@@ -339,6 +829,84 @@ impl CursorKind {
             CursorKind::Square { .. } => "Square",
             CursorKind::Circle { .. } => "Circle",
             CursorKind::Pen { .. } => "Pen",
+            CursorKind::GravityZone => "Gravity",
+            CursorKind::Attractor { .. } => "Gravity",
+            CursorKind::RigidBody => "Physics",
+            CursorKind::Shape {
+                shape: ShapeKind::Line { .. },
+            } => "Line",
+            CursorKind::Shape { .. } => "Shape",
+            CursorKind::Fill { .. } => "Fill",
+            CursorKind::Replace { .. } => "Replace",
+            CursorKind::Stamp { .. } => "Stamp",
+            CursorKind::Select { .. } | CursorKind::Paste => "Select",
+            CursorKind::Text { .. } => "Text",
+        }
+    }
+    /// The size of this brush, if it can be resized
+    pub const fn size(&self) -> Option<u32> {
+        match self {
+            CursorKind::Square { size }
+            | CursorKind::Circle { size }
+            | CursorKind::Pen { size }
+            | CursorKind::Replace { size }
+            | CursorKind::Stamp { size, .. }
+            | CursorKind::Text { size } => Some(*size),
+            _ => None,
+        }
+    }
+    /// A copy of this brush with the given size. Cursors without a size are returned unchanged.
+    pub const fn with_size(&self, size: u32) -> Self {
+        match *self {
+            CursorKind::Square { .. } => CursorKind::Square { size },
+            CursorKind::Circle { .. } => CursorKind::Circle { size },
+            CursorKind::Pen { .. } => CursorKind::Pen { size },
+            CursorKind::Replace { .. } => CursorKind::Replace { size },
+            CursorKind::Stamp { stamp, .. } => CursorKind::Stamp { stamp, size },
+            CursorKind::Text { .. } => CursorKind::Text { size },
+            cursor => cursor,
         }
     }
+    /// Draw the cells covered by this brush around the given center
+    pub fn draw_brush<D: DrawTarget>(
+        &self,
+        target: &mut D,
+        center: embedded_graphics::prelude::Point,
+        color: D::Color,
+    ) -> Result<(), D::Error> {
+        match *self {
+            CursorKind::Square { size } | CursorKind::Replace { size } => {
+                Rectangle::with_center(center, Size::new(size, size))
+                    .into_styled(PrimitiveStyle::with_fill(color))
+                    .draw(target)
+            },
+            // The pen paints round caps
+            CursorKind::Circle { size } | CursorKind::Pen { size } => {
+                Circle::with_center(center, size)
+                    .into_styled(PrimitiveStyle::with_fill(color))
+                    .draw(target)
+            },
+            CursorKind::Stamp { stamp, size } => target.draw_iter(
+                stamp
+                    .bitmap()
+                    .scaled_points(size)
+                    .map(|offset| Pixel(center + offset, color)),
+            ),
+            CursorKind::GravityZone
+            | CursorKind::Attractor { .. }
+            | CursorKind::RigidBody
+            | CursorKind::Shape { .. }
+            | CursorKind::Fill { .. }
+            | CursorKind::Select { .. }
+            | CursorKind::Paste
+            | CursorKind::Text { .. } => target.draw_iter([Pixel(center, color)]),
+        }
+    }
+    /// Whether this cursor places objects (gravity fields, rigid bodies) instead of drawing elements
+    pub const fn is_placement_tool(&self) -> bool {
+        matches!(
+            self,
+            CursorKind::GravityZone | CursorKind::Attractor { .. } | CursorKind::RigidBody
+        )
+    }
 }
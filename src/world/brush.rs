@@ -0,0 +1,357 @@
+//   sandbox - World - brush modes
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::element::{Element, ElementKind};
+use crate::world::GameWorld;
+use embedded_graphics::prelude::{Dimensions, DrawTarget, Point, Primitive};
+use embedded_graphics::primitives::{Circle, Line, PrimitiveStyle, Rectangle};
+use embedded_graphics::{Drawable, Pixel};
+use rand::{Rng, RngCore};
+
+/// The fraction of the distance to the mouse cursor the pen moves in one frame.
+/// Lower values give smoother, but more lagging strokes.
+const PEN_SMOOTHING: f32 = 0.5;
+/// The pen speed in cells per frame at which the stroke is half as wide as the pen size
+const PEN_SPEED_FALLOFF: f32 = 12.0;
+/// The minimum stroke width relative to the pen size
+const PEN_MIN_WIDTH: f32 = 0.3;
+/// The fraction of the difference to the speed-based width the stroke width adapts in one frame
+const PEN_WIDTH_SMOOTHING: f32 = 0.3;
+
+/// Modifiers that change how the square, circle and pen brushes paint elements
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrushSettings {
+    /// Only paint a random fraction of the covered cells per frame
+    pub spray: bool,
+    /// The fraction of the covered cells that is painted per frame in spray mode, between 0 and 1
+    pub spray_density: f64,
+    /// Only paint into empty cells
+    pub only_empty: bool,
+    /// Never overwrite solid elements
+    pub protect_solids: bool,
+    /// Elements that are randomly mixed into the painted element, with their share between 0 and 1.
+    /// The painted element makes up the remaining share.
+    pub mix: Vec<(Element, f32)>,
+}
+
+impl Default for BrushSettings {
+    fn default() -> Self {
+        Self {
+            spray: false,
+            spray_density: 0.1,
+            only_empty: false,
+            protect_solids: false,
+            mix: Vec::new(),
+        }
+    }
+}
+
+impl BrushSettings {
+    /// The element to paint into one cell, picked randomly from the mix
+    fn pick(&self, element: Element, rng: &mut dyn RngCore) -> Element {
+        let mut choice = rng.random::<f32>();
+        for &(mixed, share) in &self.mix {
+            if choice < share {
+                return mixed;
+            }
+            choice -= share;
+        }
+        element
+    }
+}
+
+/// The state of a pen stroke that continues over multiple frames
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PenStroke {
+    /// The smoothed pen position in board coordinates, if a stroke is in progress
+    position: Option<(f32, f32)>,
+    /// The cell the pen last moved towards and the element it draws with
+    target: Option<(Point, Element)>,
+    /// The current stroke width in cells
+    width: f32,
+}
+
+/// The cell containing the given position in board coordinates
+fn cell((x, y): (f32, f32)) -> Point {
+    Point::new(x.round() as i32, y.round() as i32)
+}
+
+impl PenStroke {
+    /// Move the pen towards the given cell and return the start, end and width of the segment to draw.
+    /// Fast movements draw thinner strokes, like a pen that is pressed more lightly.
+    fn advance(&mut self, x: i32, y: i32, size: u32, element: Element) -> (Point, Point, u32) {
+        let target = (x as f32, y as f32);
+        self.target = Some((Point::new(x, y), element));
+        let Some(from) = self.position else {
+            self.position = Some(target);
+            self.width = size as f32;
+            return (Point::new(x, y), Point::new(x, y), size);
+        };
+        let to = (
+            from.0 + (target.0 - from.0) * PEN_SMOOTHING,
+            from.1 + (target.1 - from.1) * PEN_SMOOTHING,
+        );
+        let speed = (to.0 - from.0).hypot(to.1 - from.1);
+        let pressure = (1.0 / (1.0 + speed / PEN_SPEED_FALLOFF)).max(PEN_MIN_WIDTH);
+        self.width += (size as f32 * pressure - self.width) * PEN_WIDTH_SMOOTHING;
+        self.position = Some(to);
+        (cell(from), cell(to), self.stroke_width())
+    }
+    /// The current stroke width in whole cells
+    fn stroke_width(&self) -> u32 {
+        (self.width.round() as u32).max(1)
+    }
+    /// The width the pen with the given size paints at next, i.e., the current stroke width while
+    /// a stroke is in progress
+    pub fn preview_width(&self, size: u32) -> u32 {
+        match self.position {
+            Some(_) => self.stroke_width(),
+            None => size,
+        }
+    }
+}
+
+impl GameWorld {
+    /// Continue the given pen stroke towards the given cell, drawing a segment with round caps
+    pub(crate) fn draw_pen(
+        &mut self,
+        pen: &mut PenStroke,
+        x: i32,
+        y: i32,
+        size: u32,
+        element: Element,
+        settings: &BrushSettings,
+        rng: &mut dyn RngCore,
+    ) {
+        let (from, to, width) = pen.advance(x, y, size, element);
+        self.draw_segment(from, to, width, element, settings, rng);
+    }
+    /// Finish the given pen stroke, i.e., when the mouse button is released.
+    /// The smoothed pen lags behind the cursor, so the rest of the stroke up to the cursor is drawn now.
+    pub(crate) fn end_pen(
+        &mut self,
+        pen: &mut PenStroke,
+        settings: &BrushSettings,
+        rng: &mut dyn RngCore,
+    ) {
+        if let (Some(from), Some((to, element))) = (pen.position, pen.target)
+            && cell(from) != to
+        {
+            self.draw_segment(cell(from), to, pen.stroke_width(), element, settings, rng);
+        }
+        *pen = PenStroke::default();
+    }
+    /// Draw a line segment of the given width with round caps
+    fn draw_segment(
+        &mut self,
+        from: Point,
+        to: Point,
+        width: u32,
+        element: Element,
+        settings: &BrushSettings,
+        rng: &mut dyn RngCore,
+    ) {
+        let mut brush = BrushTarget {
+            world: self,
+            settings,
+            rng,
+        };
+        for cap in [from, to] {
+            Circle::with_center(cap, width)
+                .into_styled(PrimitiveStyle::with_fill(element))
+                .draw(&mut brush)
+                .unwrap();
+        }
+        // A single dot only consists of its round cap
+        if from != to {
+            Line::new(from, to)
+                .into_styled(PrimitiveStyle::with_stroke(element, width))
+                .draw(&mut brush)
+                .unwrap();
+        }
+    }
+}
+
+/// A draw target that applies the brush settings to all elements painted into the world.
+/// Erasing (painting empty cells) only respects the spray mode.
+pub(crate) struct BrushTarget<'a> {
+    pub(crate) world: &'a mut GameWorld,
+    pub(crate) settings: &'a BrushSettings,
+    pub(crate) rng: &'a mut dyn RngCore,
+}
+
+impl Dimensions for BrushTarget<'_> {
+    fn bounding_box(&self) -> Rectangle {
+        self.world.bounding_box()
+    }
+}
+
+impl DrawTarget for BrushTarget<'_> {
+    type Color = Element;
+    type Error = String;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mut painted = Vec::new();
+        for Pixel(point, element) in pixels {
+            if point.x < 0
+                || point.y < 0
+                || point.x >= self.world.viewport_width() as i32
+                || point.y >= self.world.viewport_height() as i32
+            {
+                continue;
+            }
+            if self.settings.spray && !self.rng.random_bool(self.settings.spray_density) {
+                continue;
+            }
+            if element == Element::None {
+                painted.push(Pixel(point, element));
+                continue;
+            }
+            let current = self.world.board[point.x as usize][point.y as usize];
+            if (self.settings.only_empty && current != Element::None)
+                || (self.settings.protect_solids && current.kind() == ElementKind::Solid)
+            {
+                continue;
+            }
+            painted.push(Pixel(point, self.settings.pick(element, self.rng)));
+        }
+        self.world.draw_iter(painted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::ui::CursorKind;
+    use crate::world::GameWorld;
+    use crate::world::brush::{BrushSettings, BrushTarget, PenStroke};
+    use embedded_graphics::Drawable;
+    use embedded_graphics::prelude::{Point, Primitive, Size};
+    use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    /// Paint a 10x10 world completely with sand using the given brush settings
+    fn paint(board: &mut GameWorld, settings: &BrushSettings) {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        Rectangle::new(Point::new(0, 0), Size::new(10, 10))
+            .into_styled(PrimitiveStyle::with_fill(Element::Sand))
+            .draw(&mut BrushTarget {
+                world: board,
+                settings,
+                rng: &mut rng,
+            })
+            .unwrap();
+    }
+    fn count(board: &GameWorld, element: Element) -> usize {
+        board
+            .board
+            .iter()
+            .flatten()
+            .filter(|&&e| e == element)
+            .count()
+    }
+    #[test]
+    fn test_brush_overwrites_everything_by_default() {
+        let mut board = GameWorld::new(10, 10);
+        board.board[5][5] = Element::BrickWall;
+        paint(&mut board, &BrushSettings::default());
+        assert_eq!(count(&board, Element::Sand), 100);
+    }
+    #[test]
+    fn test_brush_protects_solids() {
+        let mut board = GameWorld::new(10, 10);
+        board.board[5][5] = Element::BrickWall;
+        board.board[6][6] = Element::Water;
+        let settings = BrushSettings {
+            protect_solids: true,
+            ..Default::default()
+        };
+        paint(&mut board, &settings);
+        assert_eq!(board.board[5][5], Element::BrickWall);
+        assert_eq!(count(&board, Element::Sand), 99);
+    }
+    #[test]
+    fn test_brush_only_paints_empty_cells() {
+        let mut board = GameWorld::new(10, 10);
+        board.board[5][5] = Element::Water;
+        let settings = BrushSettings {
+            only_empty: true,
+            ..Default::default()
+        };
+        paint(&mut board, &settings);
+        assert_eq!(board.board[5][5], Element::Water);
+        assert_eq!(count(&board, Element::Sand), 99);
+    }
+    #[test]
+    fn test_spray_paints_a_fraction_of_the_cells() {
+        let mut board = GameWorld::new(10, 10);
+        let settings = BrushSettings {
+            spray: true,
+            spray_density: 0.5,
+            ..Default::default()
+        };
+        paint(&mut board, &settings);
+        let sand = count(&board, Element::Sand);
+        assert!(sand > 20 && sand < 80);
+    }
+    #[test]
+    fn test_brush_mixes_in_other_elements() {
+        let mut board = GameWorld::new(10, 10);
+        let settings = BrushSettings {
+            mix: vec![(Element::Salt, 0.3)],
+            ..Default::default()
+        };
+        paint(&mut board, &settings);
+        let salt = count(&board, Element::Salt);
+        assert!(salt > 10 && count(&board, Element::Sand) > salt);
+        assert_eq!(count(&board, Element::Sand) + salt, 100);
+    }
+    #[test]
+    fn test_pen_draws_continuous_horizontal_stroke() {
+        let mut board = GameWorld::new(40, 10);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let mut pen = PenStroke::default();
+        let settings = BrushSettings::default();
+        for x in [2, 30] {
+            board.draw_pen(&mut pen, x, 5, 1, Element::Sand, &settings, &mut rng);
+        }
+        board.end_pen(&mut pen, &settings, &mut rng);
+        assert_eq!(pen, PenStroke::default());
+        assert!((2..=30).all(|x| board.board[x][5] == Element::Sand));
+        assert!(board.board.iter().all(|column| column[3] == Element::None));
+    }
+    #[test]
+    fn test_pen_preview_matches_painted_dot() {
+        let mut painted = GameWorld::new(20, 20);
+        let mut preview = GameWorld::new(20, 20);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let mut pen = PenStroke::default();
+        let settings = BrushSettings::default();
+        for size in [1, 2, 5, 8] {
+            CursorKind::Pen { size }
+                .with_size(pen.preview_width(size))
+                .draw_brush(&mut preview, Point::new(10, 10), Element::Sand)
+                .unwrap();
+            painted.draw_pen(&mut pen, 10, 10, size, Element::Sand, &settings, &mut rng);
+            painted.end_pen(&mut pen, &settings, &mut rng);
+            assert_eq!(painted.board, preview.board);
+        }
+    }
+}
@@ -0,0 +1,352 @@
+//   sandbox - World - creatures
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::element::{Agent, AgentState, Element, ElementKind};
+use crate::world::{GameWorld, PROBES};
+use rand::prelude::IndexedRandom;
+use rand::{Rng, RngCore};
+use std::collections::VecDeque;
+
+/// The number of population samples that are kept for the population graph
+const POPULATION_HISTORY_LENGTH: usize = 300;
+/// The number of ticks between two population samples
+const POPULATION_SAMPLE_INTERVAL: usize = 10;
+/// Creatures touching elements at least as hot as this temperature in °C burn to ash
+const DEADLY_TEMPERATURE: f32 = 500.0;
+/// The probability of a creature to move in one tick
+const ANT_SPEED: f64 = 0.4;
+const FISH_SPEED: f64 = 0.5;
+const BIRD_SPEED: f64 = 0.7;
+/// The probability of a moving creature to turn around in one tick
+const TURN_PROBABILITY: f64 = 0.03;
+/// The probability of a walking ant to start digging when touching sand
+const DIG_PROBABILITY: f64 = 0.1;
+/// The number of ticks an ant keeps digging
+const DIG_TICKS: u8 = 8;
+/// The number of ticks a fish survives out of water
+const SUFFOCATION_TICKS: u8 = 60;
+/// The probability of a fish to die in one tick when touching salt water
+const SALT_WATER_DEATH_PROBABILITY: f64 = 0.02;
+/// The probability of a flying bird to perch when landing on a surface
+const PERCH_PROBABILITY: f64 = 0.3;
+/// The number of ticks a bird rests on a surface
+const PERCH_TICKS: u8 = 80;
+
+/// The number of living creatures of each species
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Population {
+    pub ants: usize,
+    pub fish: usize,
+    pub birds: usize,
+}
+impl Population {
+    /// The population count of each species, paired with the species
+    pub fn counts(&self) -> [(Element, usize); 3] {
+        [
+            (
+                Element::Ant {
+                    agent: Agent::default(),
+                },
+                self.ants,
+            ),
+            (
+                Element::Fish {
+                    agent: Agent::default(),
+                },
+                self.fish,
+            ),
+            (
+                Element::Bird {
+                    agent: Agent::default(),
+                },
+                self.birds,
+            ),
+        ]
+    }
+}
+
+/// The given creature with its state replaced by the given agent state
+const fn with_agent(creature: Element, agent: Agent) -> Element {
+    match creature {
+        Element::Ant { .. } => Element::Ant { agent },
+        Element::Fish { .. } => Element::Fish { agent },
+        Element::Bird { .. } => Element::Bird { agent },
+        e => e,
+    }
+}
+
+impl GameWorld {
+    /// The recorded population samples, oldest first
+    pub fn population_history(&self) -> &VecDeque<Population> {
+        &self.population_history
+    }
+    /// Move all creatures and count the population
+    pub(in crate::world) fn step_creatures(&mut self, rng: &mut dyn RngCore) {
+        let mut creatures = Vec::new();
+        let mut population = Population::default();
+        for y in 0..self.viewport_height() {
+            for x in 0..self.viewport_width() {
+                match self.board[x][y] {
+                    Element::Ant { .. } => population.ants += 1,
+                    Element::Fish { .. } => population.fish += 1,
+                    Element::Bird { .. } => population.birds += 1,
+                    _ => continue,
+                }
+                creatures.push((x, y));
+            }
+        }
+        // Every creature moves at most once per tick, even if it moves into a cell that is visited later
+        for (x, y) in creatures {
+            self.step_creature(x, y, rng);
+        }
+        self.population_timer += 1;
+        if self.population_timer >= POPULATION_SAMPLE_INTERVAL {
+            self.population_timer = 0;
+            if self.population_history.len() >= POPULATION_HISTORY_LENGTH {
+                self.population_history.pop_front();
+            }
+            self.population_history.push_back(population);
+        }
+    }
+    /// Move the creature at the given position into the given cell, swapping it with the content of that cell
+    fn move_creature(&mut self, x: usize, y: usize, to: (usize, usize), agent: Agent) {
+        let creature = with_agent(self.board[x][y], agent);
+        self.board[x][y] = self.board[to.0][to.1];
+        self.board[to.0][to.1] = creature;
+    }
+    fn step_creature(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) {
+        // All creatures burn to ash when touching very hot elements
+        let burns = PROBES.iter().any(|&(x_offs, y_offs)| {
+            self.offset(x, y, x_offs, y_offs).is_some_and(|(n_x, n_y)| {
+                self.board[n_x][n_y]
+                    .temperature()
+                    .is_some_and(|t| t >= DEADLY_TEMPERATURE)
+            })
+        });
+        if burns {
+            self.board[x][y] = Element::Ash;
+            return;
+        }
+        match self.board[x][y] {
+            Element::Ant { agent } => self.step_ant(x, y, agent, rng),
+            Element::Fish { agent } => self.step_fish(x, y, agent, rng),
+            Element::Bird { agent } => self.step_bird(x, y, agent, rng),
+            _ => {},
+        }
+    }
+    /// Ants fall down without support, walk along surfaces, climb small steps and dig tunnels through sand
+    fn step_ant(&mut self, x: usize, y: usize, mut agent: Agent, rng: &mut dyn RngCore) {
+        let below = self.offset(x, y, 0, 1);
+        if let Some((b_x, b_y)) = below
+            && matches!(
+                self.board[b_x][b_y].kind(),
+                ElementKind::None | ElementKind::Liquid { .. } | ElementKind::Gas { .. }
+            )
+        {
+            agent.state = AgentState::Falling;
+            self.move_creature(x, y, (b_x, b_y), agent);
+            return;
+        }
+        if !rng.random_bool(ANT_SPEED) {
+            return;
+        }
+        let dir = agent.heading.offset();
+        match agent.state {
+            AgentState::Digging { ticks } => {
+                agent.state = match ticks {
+                    0 => AgentState::Walking,
+                    t => AgentState::Digging { ticks: t - 1 },
+                };
+                let (x_offs, y_offs) = *[(dir, 0), (dir, 1), (0, 1)].choose(rng).unwrap();
+                match self.offset(x, y, x_offs, y_offs) {
+                    Some((t_x, t_y))
+                        if matches!(self.board[t_x][t_y], Element::Sand | Element::None) =>
+                    {
+                        self.move_creature(x, y, (t_x, t_y), agent);
+                    },
+                    _ => {
+                        agent.heading = agent.heading.reversed();
+                        self.board[x][y] = Element::Ant { agent };
+                    },
+                }
+            },
+            _ => {
+                agent.state = AgentState::Walking;
+                if rng.random_bool(TURN_PROBABILITY) {
+                    agent.heading = agent.heading.reversed();
+                    self.board[x][y] = Element::Ant { agent };
+                    return;
+                }
+                let side = self.offset(x, y, dir, 0);
+                let step = self.offset(x, y, dir, -1);
+                match side.map(|(s_x, s_y)| self.board[s_x][s_y]) {
+                    Some(Element::None) => self.move_creature(x, y, side.unwrap(), agent),
+                    Some(Element::Sand) if rng.random_bool(DIG_PROBABILITY) => {
+                        agent.state = AgentState::Digging { ticks: DIG_TICKS };
+                        self.move_creature(x, y, side.unwrap(), agent);
+                    },
+                    // Climb up small steps
+                    Some(_)
+                        if step.is_some_and(|(s_x, s_y)| self.board[s_x][s_y] == Element::None) =>
+                    {
+                        self.move_creature(x, y, step.unwrap(), agent);
+                    },
+                    _ => {
+                        agent.heading = agent.heading.reversed();
+                        self.board[x][y] = Element::Ant { agent };
+                    },
+                }
+            },
+        }
+    }
+    /// Fish swim through water, die in salt water and suffocate out of water
+    fn step_fish(&mut self, x: usize, y: usize, mut agent: Agent, rng: &mut dyn RngCore) {
+        let neighbors: Vec<Element> = PROBES
+            .iter()
+            .filter_map(|&(x_offs, y_offs)| self.offset(x, y, x_offs, y_offs))
+            .map(|(n_x, n_y)| self.board[n_x][n_y])
+            .collect();
+        if neighbors
+            .iter()
+            .any(|e| matches!(e, Element::SaltWater { .. }))
+            && rng.random_bool(SALT_WATER_DEATH_PROBABILITY)
+        {
+            self.board[x][y] = Element::Dust;
+            return;
+        }
+        if !neighbors.contains(&Element::Water) {
+            let ticks = match agent.state {
+                AgentState::Stranded { ticks } => ticks + 1,
+                _ => 0,
+            };
+            if ticks >= SUFFOCATION_TICKS {
+                self.board[x][y] = Element::Dust;
+                return;
+            }
+            agent.state = AgentState::Stranded { ticks };
+            // Stranded fish fall down and flop around
+            let target = match self.offset(x, y, 0, 1) {
+                Some((b_x, b_y)) if self.board[b_x][b_y] == Element::None => Some((b_x, b_y)),
+                _ => self
+                    .offset(x, y, agent.heading.offset(), 0)
+                    .filter(|&(s_x, s_y)| {
+                        self.board[s_x][s_y] == Element::None && rng.random_bool(0.1)
+                    }),
+            };
+            match target {
+                Some(to) => self.move_creature(x, y, to, agent),
+                None => self.board[x][y] = Element::Fish { agent },
+            }
+            return;
+        }
+        agent.state = AgentState::Swimming;
+        if !rng.random_bool(FISH_SPEED) {
+            self.board[x][y] = Element::Fish { agent };
+            return;
+        }
+        if rng.random_bool(TURN_PROBABILITY) {
+            agent.heading = agent.heading.reversed();
+        }
+        let y_offs = *[-1, 0, 0, 1].choose(rng).unwrap();
+        match self.offset(x, y, agent.heading.offset(), y_offs) {
+            Some((t_x, t_y)) if self.board[t_x][t_y] == Element::Water => {
+                self.move_creature(x, y, (t_x, t_y), agent);
+            },
+            _ => {
+                agent.heading = agent.heading.reversed();
+                self.board[x][y] = Element::Fish { agent };
+            },
+        }
+    }
+    /// Birds fly through the air and rest on surfaces from time to time
+    fn step_bird(&mut self, x: usize, y: usize, mut agent: Agent, rng: &mut dyn RngCore) {
+        if let AgentState::Perching { ticks } = agent.state
+            && ticks > 0
+        {
+            agent.state = AgentState::Perching { ticks: ticks - 1 };
+            self.board[x][y] = Element::Bird { agent };
+            return;
+        }
+        agent.state = AgentState::Flying;
+        if !rng.random_bool(BIRD_SPEED) {
+            self.board[x][y] = Element::Bird { agent };
+            return;
+        }
+        if rng.random_bool(TURN_PROBABILITY) {
+            agent.heading = agent.heading.reversed();
+        }
+        let y_offs = *[-1, 0, 0, 1].choose(rng).unwrap();
+        match self.offset(x, y, agent.heading.offset(), y_offs) {
+            Some((t_x, t_y)) if self.board[t_x][t_y] == Element::None => {
+                self.move_creature(x, y, (t_x, t_y), agent);
+            },
+            // Land on solid ground and powders
+            Some((t_x, t_y))
+                if y_offs >= 0
+                    && matches!(
+                        self.board[t_x][t_y].kind(),
+                        ElementKind::Solid | ElementKind::Powder { .. }
+                    )
+                    && rng.random_bool(PERCH_PROBABILITY) =>
+            {
+                agent.state = AgentState::Perching { ticks: PERCH_TICKS };
+                self.board[x][y] = Element::Bird { agent };
+            },
+            _ => {
+                agent.heading = agent.heading.reversed();
+                self.board[x][y] = Element::Bird { agent };
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::{Agent, Element};
+    use crate::world::GameWorld;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_fish_suffocates_out_of_water() {
+        let mut board = GameWorld::new(3, 3);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[1][2] = Element::Fish {
+            agent: Agent::default(),
+        };
+        for _ in 0..100 {
+            board.tick(&mut rng);
+        }
+        assert!(
+            board
+                .board
+                .iter()
+                .flatten()
+                .all(|e| !matches!(e, Element::Fish { .. }))
+        );
+    }
+    #[test]
+    fn test_creatures_burn_to_ash() {
+        let mut board = GameWorld::new(3, 1);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[0][0] = Element::FireSource;
+        board.board[1][0] = Element::Ant {
+            agent: Agent::default(),
+        };
+        board.tick(&mut rng);
+        assert_eq!(board.board[1][0], Element::Ash);
+    }
+}
@@ -0,0 +1,430 @@
+//   sandbox - World - gravity zones and attractors
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::element::{AIR_DENSITY, Element, ElementKind};
+use crate::world::{GameWorld, Move};
+use rand::{Rng, RngCore};
+use strum_macros::EnumIter;
+
+/// The radius around the center of a black or white hole in which elements are consumed or emitted
+pub const HOLE_CORE_RADIUS: f32 = 2.5;
+/// The probability of a white hole to emit an element into an empty cell of its core in one tick
+const WHITE_HOLE_EMIT_PROBABILITY: f64 = 0.05;
+/// The probability of a black hole to consume an element inside its core in one tick
+const BLACK_HOLE_CONSUME_PROBABILITY: f64 = 0.5;
+
+#[derive(Copy, Clone, PartialEq, Debug, Default, EnumIter)]
+pub enum AttractorKind {
+    /// Pulls all elements towards its center
+    #[default]
+    Attractor,
+    /// Pushes all elements away from its center
+    Repulsor,
+    /// Pulls all elements towards its center and consumes them
+    BlackHole,
+    /// Pushes all elements away from its center and emits an element
+    WhiteHole,
+}
+
+impl AttractorKind {
+    /// Whether this kind pulls elements towards its center
+    pub const fn attracts(&self) -> bool {
+        match self {
+            AttractorKind::Attractor => true,
+            AttractorKind::Repulsor => false,
+            AttractorKind::BlackHole => true,
+            AttractorKind::WhiteHole => false,
+        }
+    }
+    pub const fn ui_description(&self) -> &'static str {
+        match self {
+            AttractorKind::Attractor => "Pulls all elements towards its center.",
+            AttractorKind::Repulsor => "Pushes all elements away from its center.",
+            AttractorKind::BlackHole => "Pulls all elements towards its center and consumes them.",
+            AttractorKind::WhiteHole => {
+                "Pushes all elements away from its center and emits the selected element."
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for AttractorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AttractorKind::Attractor => "Attractor",
+                AttractorKind::Repulsor => "Repulsor",
+                AttractorKind::BlackHole => "Black Hole",
+                AttractorKind::WhiteHole => "White Hole",
+            }
+        )
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GravityField {
+    /// A rectangular zone that overrides the gravity vector of all elements inside it.
+    /// A gravity of (0, 1) corresponds to the global gravity.
+    Zone {
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        gravity: (f32, f32),
+    },
+    /// A point-shaped source of gravity.
+    /// The force decreases linearly from `strength` at the center to zero at `radius`.
+    Attractor {
+        x: usize,
+        y: usize,
+        radius: usize,
+        strength: f32,
+        kind: AttractorKind,
+        /// The element that is emitted by white holes
+        emits: Element,
+    },
+}
+
+impl GravityField {
+    /// Check if the given board coordinate is affected by this field
+    pub fn contains(&self, px: usize, py: usize) -> bool {
+        match self {
+            GravityField::Zone {
+                x,
+                y,
+                width,
+                height,
+                ..
+            } => px >= *x && px < x + width && py >= *y && py < y + height,
+            GravityField::Attractor { x, y, radius, .. } => {
+                distance(*x, *y, px, py) < *radius as f32
+            },
+        }
+    }
+}
+
+fn distance(x1: usize, y1: usize, x2: usize, y2: usize) -> f32 {
+    let dx = x2 as f32 - x1 as f32;
+    let dy = y2 as f32 - y1 as f32;
+    (dx * dx + dy * dy).sqrt()
+}
+
+impl GameWorld {
+    pub fn gravity_fields(&self) -> &Vec<GravityField> {
+        &self.gravity_fields
+    }
+    pub fn add_gravity_field(&mut self, field: GravityField) {
+        self.gravity_fields.push(field);
+        self.mark_changed();
+    }
+    /// Remove all gravity fields that affect the given board coordinate
+    pub fn remove_gravity_fields_at(&mut self, x: usize, y: usize) {
+        let count = self.gravity_fields.len();
+        self.gravity_fields.retain(|f| !f.contains(x, y));
+        if self.gravity_fields.len() != count {
+            self.mark_changed();
+        }
+    }
+    pub fn clear_gravity_fields(&mut self) {
+        if !self.gravity_fields.is_empty() {
+            self.gravity_fields.clear();
+            self.mark_changed();
+        }
+    }
+    /// Calculate the gravity vector acting on the element at the given position.
+    /// Returns None if the element is only affected by the global gravity.
+    pub(in crate::world) fn field_force(&self, x: usize, y: usize) -> Option<(f32, f32)> {
+        if self.gravity_fields.is_empty() {
+            return None;
+        }
+        let density = match self.board[x][y].kind() {
+            ElementKind::None | ElementKind::Solid => return None,
+            ElementKind::Powder { density } => density,
+            ElementKind::Liquid { density } => density,
+            ElementKind::Gas { density } => density,
+        };
+        let mut base = None;
+        let mut pull = (0.0f32, 0.0f32);
+        let mut affected = false;
+        for field in &self.gravity_fields {
+            match field {
+                GravityField::Zone { gravity, .. } => {
+                    if field.contains(x, y) {
+                        // The most recently placed zone wins
+                        base = Some(*gravity);
+                        affected = true;
+                    }
+                },
+                GravityField::Attractor {
+                    x: ax,
+                    y: ay,
+                    radius,
+                    strength,
+                    kind,
+                    ..
+                } => {
+                    let d = distance(*ax, *ay, x, y);
+                    if d >= *radius as f32 || d == 0.0 {
+                        continue;
+                    }
+                    affected = true;
+                    let f = strength * (1.0 - d / *radius as f32);
+                    let dir = if kind.attracts() { -1.0 } else { 1.0 };
+                    pull.0 += dir * f * (x as f32 - *ax as f32) / d;
+                    pull.1 += dir * f * (y as f32 - *ay as f32) / d;
+                },
+            }
+        }
+        if !affected {
+            return None;
+        }
+        // Outside of zones, elements are still subject to the global gravity.
+        // Elements lighter than air rise against the gravity of zones, just like against the
+        // global gravity.
+        let (gx, gy) = base.unwrap_or((0.0, 1.0));
+        let buoyancy = if density > AIR_DENSITY { 1.0 } else { -1.0 };
+        Some((buoyancy * gx + pull.0, buoyancy * gy + pull.1))
+    }
+    /// Try to push a move along the given gravity vector to the moves vector and return true if that succeeded.
+    /// Heavier elements react stronger to the given force.
+    pub(in crate::world) fn move_along(
+        &mut self,
+        x: usize,
+        y: usize,
+        force: (f32, f32),
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        let magnitude = (force.0 * force.0 + force.1 * force.1).sqrt();
+        if magnitude < f32::EPSILON {
+            return false;
+        }
+        let p_move = (magnitude as f64 * self.board[x][y].field_response()).min(1.0);
+        if !rng.random_bool(p_move) {
+            return false;
+        }
+        // Randomly round the normalized force to a neighbor cell
+        let (ux, uy) = (force.0 / magnitude, force.1 / magnitude);
+        let mut sx = if rng.random_bool(ux.abs() as f64) {
+            ux.signum() as i32
+        } else {
+            0
+        };
+        let mut sy = if rng.random_bool(uy.abs() as f64) {
+            uy.signum() as i32
+        } else {
+            0
+        };
+        if sx == 0 && sy == 0 {
+            if ux.abs() >= uy.abs() {
+                sx = ux.signum() as i32;
+            } else {
+                sy = uy.signum() as i32;
+            }
+        }
+        // Try the direct direction first, then both 45° directions, then (for fluids) both 90° directions
+        let mut candidates = [
+            (sx, sy),
+            rotate45(sx, sy, true),
+            rotate45(sx, sy, false),
+            (0, 0),
+            (0, 0),
+        ];
+        if rng.random_bool(0.5) {
+            candidates.swap(1, 2);
+        }
+        let count = if self.board[x][y].is_liquid_or_gas() {
+            candidates[3] = rotate45(candidates[1].0, candidates[1].1, true);
+            candidates[4] = rotate45(candidates[2].0, candidates[2].1, false);
+            5
+        } else {
+            3
+        };
+        for &(dx, dy) in &candidates[..count] {
+            let tx = x as i32 + dx;
+            let ty = y as i32 + dy;
+            if tx < 0
+                || ty < 0
+                || tx >= self.viewport_width() as i32
+                || ty >= self.viewport_height() as i32
+            {
+                continue;
+            }
+            let (tx, ty) = (tx as usize, ty as usize);
+            if self.board[tx][ty] == Element::None {
+                self.moves.push(Move::MoveElement {
+                    from_x: x,
+                    from_y: y,
+                    to_x: tx,
+                    to_y: ty,
+                });
+                return true;
+            }
+            // Heavier elements sink through lighter liquids and gases along the field
+            if self.board[tx][ty].is_liquid_or_gas()
+                && let (Some(a), Some(b)) =
+                    (self.board[x][y].density(), self.board[tx][ty].density())
+                && b < a
+                && rng.random_bool(1. - (b / a) as f64)
+            {
+                self.moves.push(Move::SwapElement {
+                    from_x: x,
+                    from_y: y,
+                    to_x: tx,
+                    to_y: ty,
+                });
+                return true;
+            }
+        }
+        false
+    }
+    /// Let black holes consume and white holes emit elements
+    pub(in crate::world) fn apply_field_sources(&mut self, rng: &mut dyn RngCore) {
+        let width = self.viewport_width();
+        let height = self.viewport_height();
+        for i in 0..self.gravity_fields.len() {
+            let GravityField::Attractor {
+                x: ax,
+                y: ay,
+                kind,
+                emits,
+                ..
+            } = self.gravity_fields[i]
+            else {
+                continue;
+            };
+            if !matches!(kind, AttractorKind::BlackHole | AttractorKind::WhiteHole) {
+                continue;
+            }
+            let r = HOLE_CORE_RADIUS.ceil() as usize;
+            for x in ax.saturating_sub(r)..=(ax + r).min(width - 1) {
+                for y in ay.saturating_sub(r)..=(ay + r).min(height - 1) {
+                    if distance(ax, ay, x, y) > HOLE_CORE_RADIUS {
+                        continue;
+                    }
+                    match kind {
+                        AttractorKind::BlackHole
+                            if !matches!(
+                                self.board[x][y].kind(),
+                                ElementKind::None | ElementKind::Solid
+                            ) && rng.random_bool(BLACK_HOLE_CONSUME_PROBABILITY) =>
+                        {
+                            self.board[x][y] = Element::None;
+                        },
+                        AttractorKind::WhiteHole
+                            if self.board[x][y] == Element::None
+                                && rng.random_bool(WHITE_HOLE_EMIT_PROBABILITY) =>
+                        {
+                            self.board[x][y] = emits;
+                        },
+                        _ => {},
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Rotate the given unit step by 45 degrees
+const fn rotate45(dx: i32, dy: i32, clockwise: bool) -> (i32, i32) {
+    // Neighbor directions in clockwise order, starting at the top
+    const DIRS: [(i32, i32); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+    let mut i = 0;
+    while i < DIRS.len() {
+        if DIRS[i].0 == dx && DIRS[i].1 == dy {
+            return if clockwise {
+                DIRS[(i + 1) % DIRS.len()]
+            } else {
+                DIRS[(i + DIRS.len() - 1) % DIRS.len()]
+            };
+        }
+        i += 1;
+    }
+    (dx, dy)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::world::GameWorld;
+    use crate::world::gravity::{AttractorKind, GravityField};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_zone_reverses_gravity() {
+        let mut board = GameWorld::new(3, 5);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.add_gravity_field(GravityField::Zone {
+            x: 0,
+            y: 0,
+            width: 3,
+            height: 5,
+            gravity: (0.0, -1.0),
+        });
+        board.board[1][4] = Element::Lava;
+        for _ in 0..20 {
+            board.tick(&mut rng);
+        }
+        // The lava rises up and spreads along the ceiling
+        assert!((0..3).any(|x| board.board[x][0] == Element::Lava));
+    }
+    #[test]
+    fn test_light_gases_rise_against_zones() {
+        let mut board = GameWorld::new(3, 5);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.add_gravity_field(GravityField::Zone {
+            x: 0,
+            y: 0,
+            width: 3,
+            height: 5,
+            gravity: (0.0, 1.0),
+        });
+        board.board[1][4] = Element::Hydrogen;
+        for _ in 0..20 {
+            board.tick(&mut rng);
+        }
+        assert!((0..3).any(|x| board.board[x][0] == Element::Hydrogen));
+    }
+    #[test]
+    fn test_black_hole_consumes() {
+        let mut board = GameWorld::new(9, 9);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.add_gravity_field(GravityField::Attractor {
+            x: 4,
+            y: 4,
+            radius: 8,
+            strength: 5.0,
+            kind: AttractorKind::BlackHole,
+            emits: Element::None,
+        });
+        board.board[4][1] = Element::Sand;
+        for _ in 0..50 {
+            board.tick(&mut rng);
+        }
+        assert!(board.board.iter().flatten().all(|e| *e == Element::None));
+    }
+}
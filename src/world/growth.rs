@@ -0,0 +1,191 @@
+//   sandbox - World - plant growth
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::element::{Element, Hydration, MAX_HYDRATION};
+use crate::world::{GameWorld, PROBES};
+use rand::prelude::IndexedRandom;
+use rand::{Rng, RngCore};
+
+/// The probability of a seed to sprout in one tick if it is resting on soil next to water
+const SPROUT_PROBABILITY: f64 = 0.01;
+/// The probability of a plant cell to absorb an adjacent water cell in one tick
+const ABSORB_PROBABILITY: f64 = 0.02;
+/// The probability of a plant cell to use up one unit of water in one tick
+const THIRST_PROBABILITY: f64 = 0.01;
+/// The probability of a completely dry plant cell to wither to dust in one tick
+const WITHER_PROBABILITY: f64 = 0.002;
+/// The probability of a plant cell to grow in one tick
+const GROWTH_PROBABILITY: f64 = 0.03;
+/// The probability of a growing plant to branch to the side instead of growing upward
+const BRANCH_PROBABILITY: f64 = 0.2;
+/// The minimum hydration a plant cell needs to grow
+const GROWTH_HYDRATION: u8 = 10;
+/// The probability of a vine to grow downward in one tick
+const VINE_GROWTH_PROBABILITY: f64 = 0.01;
+/// The maximum length of a hanging vine
+const VINE_MAX_LENGTH: usize = 25;
+/// The probability of a fungus to spread in one tick
+const FUNGUS_SPREAD_PROBABILITY: f64 = 0.005;
+
+impl GameWorld {
+    /// The number of plant cells in the 8-neighborhood of the given cell
+    fn plant_neighbors(&self, x: usize, y: usize) -> usize {
+        (-1..=1)
+            .flat_map(|x_offs| (-1..=1).map(move |y_offs| (x_offs, y_offs)))
+            .filter(|&offs| offs != (0, 0))
+            .filter_map(|(x_offs, y_offs)| self.offset(x, y, x_offs, y_offs))
+            .filter(|&(n_x, n_y)| matches!(self.board[n_x][n_y], Element::Plant { .. }))
+            .count()
+    }
+    /// Let seeds sprout and plants, vines and fungi grow
+    pub(in crate::world) fn grow(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) {
+        match self.board[x][y] {
+            Element::Seed => self.sprout(x, y, rng),
+            Element::Plant { hydration } => self.grow_plant(x, y, hydration.0, rng),
+            Element::Vine => self.grow_vine(x, y, rng),
+            Element::Fungus => self.spread_fungus(x, y, rng),
+            _ => {},
+        }
+    }
+    /// Seeds resting on sand or wet dust sprout when touching water, using up the water
+    fn sprout(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) {
+        let on_soil = self.offset(x, y, 0, 1).is_some_and(|(s_x, s_y)| {
+            matches!(self.board[s_x][s_y], Element::Sand | Element::WetDust)
+        });
+        if !on_soil {
+            return;
+        }
+        let water = PROBES
+            .iter()
+            .filter_map(|&(x_offs, y_offs)| self.offset(x, y, x_offs, y_offs))
+            .find(|&(w_x, w_y)| self.board[w_x][w_y] == Element::Water);
+        if let Some((w_x, w_y)) = water
+            && rng.random_bool(SPROUT_PROBABILITY)
+        {
+            self.board[w_x][w_y] = Element::None;
+            self.board[x][y] = Element::Plant {
+                hydration: Hydration::default(),
+            };
+        }
+    }
+    /// Plants absorb adjacent water and share it with adjacent plant cells.
+    /// Hydrated plants grow upward and branch to the side, while dry plants wilt and wither.
+    fn grow_plant(&mut self, x: usize, y: usize, mut hydration: u8, rng: &mut dyn RngCore) {
+        for (x_offs, y_offs) in PROBES {
+            let Some((n_x, n_y)) = self.offset(x, y, x_offs, y_offs) else {
+                continue;
+            };
+            match self.board[n_x][n_y] {
+                Element::Water if rng.random_bool(ABSORB_PROBABILITY) => {
+                    self.board[n_x][n_y] = Element::None;
+                    hydration = MAX_HYDRATION;
+                },
+                Element::WetDust if rng.random_bool(ABSORB_PROBABILITY) => {
+                    self.board[n_x][n_y] = Element::Dust;
+                    hydration = MAX_HYDRATION;
+                },
+                // Water is passed on through the plant, getting less with every cell
+                Element::Plant { hydration: other } => {
+                    hydration = hydration.max(other.0.saturating_sub(1));
+                },
+                _ => {},
+            }
+        }
+        if hydration > 0 && rng.random_bool(THIRST_PROBABILITY) {
+            hydration -= 1;
+        }
+        if hydration == 0 && rng.random_bool(WITHER_PROBABILITY) {
+            self.board[x][y] = Element::Dust;
+            return;
+        }
+        self.board[x][y] = Element::Plant {
+            hydration: Hydration(hydration),
+        };
+        if hydration < GROWTH_HYDRATION || !rng.random_bool(GROWTH_PROBABILITY) {
+            return;
+        }
+        // Grow upward unless the plant gets too dense, branches only grow into free space
+        let (x_offs, max_neighbors) = if rng.random_bool(BRANCH_PROBABILITY) {
+            (*[-1, 1].choose(rng).unwrap(), 1)
+        } else {
+            (0, 2)
+        };
+        if let Some((g_x, g_y)) = self.offset(x, y, x_offs, -1)
+            && self.board[g_x][g_y] == Element::None
+            && self.plant_neighbors(g_x, g_y) <= max_neighbors
+        {
+            self.board[g_x][g_y] = Element::Plant {
+                hydration: Hydration(hydration - 1),
+            };
+        }
+    }
+    /// Vines grow downward until they reach their maximum length
+    fn grow_vine(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) {
+        if let Some((g_x, g_y)) = self.offset(x, y, 0, 1)
+            && self.board[g_x][g_y] == Element::None
+            && rng.random_bool(VINE_GROWTH_PROBABILITY)
+        {
+            let length = (0..=y)
+                .rev()
+                .take_while(|&v_y| self.board[x][v_y] == Element::Vine)
+                .count();
+            if length < VINE_MAX_LENGTH {
+                self.board[g_x][g_y] = Element::Vine;
+            }
+        }
+    }
+    /// Fungi spread into empty cells along wood
+    fn spread_fungus(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) {
+        if !rng.random_bool(FUNGUS_SPREAD_PROBABILITY) {
+            return;
+        }
+        let (x_offs, y_offs) = *PROBES.choose(rng).unwrap();
+        if let Some((g_x, g_y)) = self.offset(x, y, x_offs, y_offs)
+            && self.board[g_x][g_y] == Element::None
+            && PROBES.iter().any(|&(w_x_offs, w_y_offs)| {
+                self.offset(g_x, g_y, w_x_offs, w_y_offs)
+                    .is_some_and(|(w_x, w_y)| self.board[w_x][w_y] == Element::Wood)
+            })
+        {
+            self.board[g_x][g_y] = Element::Fungus;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::world::GameWorld;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_seed_sprouts_and_grows_upward() {
+        let mut board = GameWorld::new(3, 10);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for x in 0..3 {
+            board.board[x][9] = Element::Sand;
+        }
+        board.board[0][8] = Element::Water;
+        board.board[1][8] = Element::Seed;
+        board.board[2][8] = Element::Water;
+        for _ in 0..1000 {
+            board.tick(&mut rng);
+        }
+        assert!(matches!(board.board[1][8], Element::Plant { .. }));
+        assert!((0..3).any(|x| matches!(board.board[x][5], Element::Plant { .. })));
+    }
+}
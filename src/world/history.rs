@@ -0,0 +1,200 @@
+//   sandbox - World - undo history
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::element::Element;
+use crate::world::GameWorld;
+use crate::world::gravity::GravityField;
+use crate::world::rigid_body::RigidBody;
+use std::collections::VecDeque;
+
+/// The maximum number of changes that can be undone
+const MAX_UNDO_STEPS: usize = 20;
+/// The maximum number of cells remembered by all undo steps together.
+/// The oldest steps are forgotten first, but the latest step is always kept.
+const MAX_UNDO_CELLS: usize = 1 << 22;
+
+/// The complete world before one change made by the user
+struct Step {
+    board: Vec<Vec<Element>>,
+    oxygen: Vec<Vec<u8>>,
+    gravity_fields: Vec<GravityField>,
+    rigid_bodies: Vec<RigidBody>,
+    /// Whether the user changed the world since this step was taken.
+    /// Steps without changes are replaced by the next checkpoint and skipped by undo.
+    changed: bool,
+}
+
+impl Step {
+    /// The number of cells remembered by this step
+    fn size(&self) -> usize {
+        self.board.len() * self.board.first().map_or(0, Vec::len)
+    }
+}
+
+/// The states of the world before the last changes made by the user
+#[derive(Default)]
+pub(super) struct History {
+    steps: VecDeque<Step>,
+}
+
+impl History {
+    /// Forget the oldest steps until the history fits into its limits again
+    fn shrink(&mut self) {
+        while self.steps.len() > MAX_UNDO_STEPS
+            || (self.steps.len() > 1
+                && self.steps.iter().map(Step::size).sum::<usize>() > MAX_UNDO_CELLS)
+        {
+            self.steps.pop_front();
+        }
+    }
+}
+
+impl GameWorld {
+    /// Remember the complete world before the user changes it, so that the change can be undone
+    pub fn checkpoint(&mut self) {
+        // A checkpoint that was not followed by any change, e.g., a click that only selected a
+        // polygon vertex, is not worth an undo step of its own
+        if self.history.steps.back().is_some_and(|step| !step.changed) {
+            self.history.steps.pop_back();
+        }
+        self.history.steps.push_back(Step {
+            board: self.board.clone(),
+            oxygen: self.oxygen.clone(),
+            gravity_fields: self.gravity_fields.clone(),
+            rigid_bodies: self.rigid_bodies.clone(),
+            changed: false,
+        });
+        self.history.shrink();
+    }
+    /// Note that the user changed the world since the last checkpoint
+    pub(in crate::world) fn mark_changed(&mut self) {
+        if let Some(step) = self.history.steps.back_mut() {
+            step.changed = true;
+        }
+    }
+    /// Restore the world from before the last change made by the user.
+    /// Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        while let Some(step) = self.history.steps.pop_back() {
+            if step.changed {
+                self.board = step.board;
+                self.oxygen = step.oxygen;
+                self.gravity_fields = step.gravity_fields;
+                self.rigid_bodies = step.rigid_bodies;
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::world::GameWorld;
+    use crate::world::gravity::GravityField;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_undo_restores_cells() {
+        let mut board = GameWorld::new(5, 5);
+        for element in [Element::Sand, Element::Water, Element::Stone] {
+            board.checkpoint();
+            board.set_element(2, 2, element);
+        }
+        assert!(board.undo());
+        assert_eq!(board.board[2][2], Element::Water);
+        assert!(board.undo());
+        assert!(board.undo());
+        assert_eq!(board.board[2][2], Element::None);
+        assert!(!board.undo());
+    }
+    #[test]
+    fn test_undo_restores_rigid_bodies_without_copies() {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let mut board = GameWorld::new(16, 16);
+        for x in 4..8 {
+            board.set_element(x, 2, Element::Wood);
+        }
+        let before = board.board.clone();
+        board.checkpoint();
+        board.create_rigid_bodies(0, 0, 15, 15);
+        board.add_gravity_field(GravityField::Zone {
+            x: 0,
+            y: 0,
+            width: 16,
+            height: 16,
+            gravity: (0.0, 2.0),
+        });
+        for _ in 0..20 {
+            board.tick(&mut rng);
+        }
+        assert_ne!(board.board, before);
+        assert!(board.undo());
+        assert_eq!(board.board, before);
+        assert!(board.rigid_bodies().is_empty());
+        assert!(board.gravity_fields().is_empty());
+    }
+    #[test]
+    fn test_undo_moves_rigid_bodies_back() {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let mut board = GameWorld::new(16, 16);
+        for x in 4..8 {
+            board.set_element(x, 2, Element::Wood);
+        }
+        board.create_rigid_bodies(0, 0, 15, 15);
+        let before = board.board.clone();
+        board.checkpoint();
+        board.set_element(15, 0, Element::BrickWall);
+        for _ in 0..20 {
+            board.tick(&mut rng);
+        }
+        assert_ne!(board.board, before);
+        assert!(board.undo());
+        assert_eq!(board.board, before);
+        assert_eq!(board.rigid_bodies().len(), 1);
+    }
+    #[test]
+    fn test_undo_reverts_the_simulation_since_the_change() {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let mut board = GameWorld::new(16, 16);
+        board.set_element(3, 15, Element::BrickWall);
+        let before = (board.board.clone(), board.oxygen.clone());
+        board.checkpoint();
+        board.set_element(8, 0, Element::Sand);
+        for _ in 0..20 {
+            board.tick(&mut rng);
+        }
+        // The sand fell away from where it was painted
+        assert_ne!(board.board[8][0], Element::Sand);
+        assert!(board.board.iter().flatten().any(|&e| e == Element::Sand));
+        assert!(board.undo());
+        assert_eq!((board.board.clone(), board.oxygen.clone()), before);
+    }
+    #[test]
+    fn test_checkpoints_without_changes_are_skipped() {
+        let mut board = GameWorld::new(5, 5);
+        board.checkpoint();
+        board.set_element(2, 2, Element::Sand);
+        for _ in 0..30 {
+            board.checkpoint();
+        }
+        assert!(board.undo());
+        assert_eq!(board.board[2][2], Element::None);
+        assert!(!board.undo());
+    }
+}
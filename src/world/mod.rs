@@ -15,17 +15,57 @@
 //   along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::element::{AIR_DENSITY, Element, ElementKind};
+use crate::shapes::ShapeKind;
+use crate::text::draw_text;
 use crate::ui::{CursorKind, Ui};
+use crate::world::brush::{BrushSettings, BrushTarget, PenStroke};
+use crate::world::creatures::Population;
+use crate::world::gravity::GravityField;
+use crate::world::history::History;
+use crate::world::oxygen::MAX_OXYGEN;
+use crate::world::rigid_body::RigidBody;
+use embedded_graphics::Pixel;
 use embedded_graphics::draw_target::DrawTarget;
 use embedded_graphics::geometry::{Dimensions, Point, Size};
 use embedded_graphics::pixelcolor::raw::RawU32;
-use embedded_graphics::prelude::{PixelColor, Primitive};
-use embedded_graphics::primitives::{Circle, Line, PrimitiveStyle, Rectangle};
-use embedded_graphics::{Drawable, Pixel};
+use embedded_graphics::prelude::PixelColor;
+use embedded_graphics::primitives::Rectangle;
+use rand::prelude::IndexedRandom;
 use rand::{Rng, RngCore};
-use std::cmp::{Ordering, max};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::mem::discriminant;
 
+pub mod brush;
+mod creatures;
+pub mod gravity;
+mod growth;
+mod history;
+mod oxygen;
+pub mod reactions;
+mod rigid_body;
+pub mod snippet;
+mod solutions;
+mod tools;
 mod transmute;
+mod world_file;
+
+/// The default width of the world in cells
+pub const DEFAULT_WORLD_WIDTH: usize = 800;
+/// The default height of the world in cells
+pub const DEFAULT_WORLD_HEIGHT: usize = 400;
+/// The minimum width and height of a world in cells
+pub const MIN_WORLD_SIZE: usize = 16;
+/// The maximum width and height of a world in cells
+pub const MAX_WORLD_SIZE: usize = 2048;
+/// The default drag of liquids
+const DEFAULT_LIQUID_DRAG: f32 = 0.15;
+/// The probability of a liquid or gas to swap with an adjacent miscible element in one tick
+const DIFFUSION_PROBABILITY: f64 = 0.1;
+/// The factor for the probability of miscible elements to separate by density
+const MISCIBLE_SETTLING_PROBABILITY: f64 = 0.01;
+/// The offsets of the four adjacent cells of a cell
+const PROBES: [(i32, i32); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
 enum Move {
     /// Move the source element to the empty target location
     MoveElement {
@@ -78,16 +118,30 @@ pub struct GameWorld {
     /// The content of the game board.
     /// Must be at least as large as the viewport size, but may be larger.
     board: Vec<Vec<Element>>,
-    /// The viewport width of the game board.
-    /// All parts of the board outside the visible area (if e.g., a window is resized to a smaller size)
-    /// are being paused until the window is resized again.
+    /// The oxygen content of each cell, between 0 and [MAX_OXYGEN].
+    /// Only air and oxygen gas can hold oxygen, but all elements carry it along while moving.
+    oxygen: Vec<Vec<u8>>,
+    /// The width of the simulated world.
+    /// It is chosen when the world is created and does not depend on the window size.
     width: usize,
-    /// The viewport height of the game board
-    /// All parts of the board outside the visible area (if e.g., a window is resized to a smaller size)
-    ///  are being paused until the window is resized again.
+    /// The height of the simulated world.
+    /// It is chosen when the world is created and does not depend on the window size.
     height: usize,
     /// All simulated element moves in one tick
     moves: Vec<Move>,
+    /// All gravity zones and attractors placed in the world
+    gravity_fields: Vec<GravityField>,
+    /// All rigid bodies moving through the world
+    rigid_bodies: Vec<RigidBody>,
+    /// The drag of liquids, between 0 and 1.
+    /// Slows down powders and rigid bodies that rise or sink in liquids.
+    liquid_drag: f32,
+    /// The recorded creature population, sampled in a fixed tick interval
+    population_history: VecDeque<Population>,
+    /// The number of ticks since the last population sample
+    population_timer: usize,
+    /// The cells before the last changes made by the user
+    history: History,
 }
 
 impl GameWorld {
@@ -107,27 +161,6 @@ impl GameWorld {
     pub const fn viewport_width(&self) -> usize {
         self.width
     }
-    /// Destroy this board and return a resized copy.
-    /// Resizes the viewport to the given size.
-    /// If the new size is less than the old size, the pruned data will continue to exist,
-    /// but not be simulated until the board is resized again.
-    #[allow(dead_code)]
-    pub(crate) fn resize(&mut self, new_width: usize, new_height: usize) {
-        let new_board_width = max(4, new_width);
-        let new_board_height = max(4, new_height);
-        let oldlen = self.board.len();
-        for x in 0..new_board_width {
-            if x >= oldlen {
-                self.board.push(vec![Element::None; new_board_height]);
-            }
-            if (new_board_height - 1) >= self.board[x].len() {
-                let oldln = new_board_height - self.board[x].len();
-                self.board[x].append(&mut vec![Element::None; oldln])
-            }
-        }
-        self.width = new_width;
-        self.height = new_height;
-    }
 }
 impl PixelColor for Element {
     type Raw = RawU32;
@@ -154,7 +187,7 @@ impl DrawTarget for GameWorld {
                 && pixel.0.x < self.viewport_width() as i32
                 && pixel.0.y < self.viewport_height() as i32
             {
-                self.board[pixel.0.x as usize][pixel.0.y as usize] = pixel.1;
+                self.set_element(pixel.0.x as usize, pixel.0.y as usize, pixel.1);
             }
         }
         Ok(())
@@ -177,48 +210,72 @@ impl GameWorld {
         element: Element,
         prev_x: i32,
         prev_y: i32,
+        pen: &mut PenStroke,
+        rng: &mut dyn RngCore,
     ) {
         if let Some((x, y)) = ui.window_to_board_coordinate(window_x, window_y) {
             match ui.cursor() {
-                CursorKind::Square { size } => {
-                    Rectangle::with_center(Point::new(x, y), Size::new(*size, *size))
-                        .into_styled(PrimitiveStyle::with_fill(element))
-                        .draw(self)
+                CursorKind::Square { .. }
+                | CursorKind::Circle { .. }
+                | CursorKind::Stamp { .. } => {
+                    let mut brush = self.brush(ui, rng);
+                    ui.cursor()
+                        .draw_brush(&mut brush, Point::new(x, y), element)
                         .unwrap();
                 },
-                CursorKind::Circle { size } => {
-                    Circle::with_center(Point::new(x, y), *size)
-                        .into_styled(PrimitiveStyle::with_fill(element))
-                        .draw(self)
-                        .unwrap();
+                CursorKind::Fill { diagonal } => {
+                    // Only fill once per click, the filled region may change while the button is held
+                    if ui.window_to_board_coordinate(prev_x, prev_y).is_none() {
+                        self.flood_fill(x as usize, y as usize, element, *diagonal);
+                    }
                 },
-                CursorKind::Pen { size } => {
-                    if let Some((px, py)) = ui.window_to_board_coordinate(prev_x, prev_y)
-                        && px != x
-                        && py != y
-                    {
-                        Rectangle::with_center(Point::new(x, y), Size::new(*size, *size))
-                            .into_styled(PrimitiveStyle::with_fill(element))
-                            .draw(self)
-                            .unwrap();
-                        Rectangle::with_center(Point::new(px, py), Size::new(*size, *size))
-                            .into_styled(PrimitiveStyle::with_fill(element))
-                            .draw(self)
-                            .unwrap();
-                        Line::new(Point::new(x, y), Point::new(px, py))
-                            .into_styled(PrimitiveStyle::with_stroke(element, *size))
-                            .draw(self)
-                            .unwrap();
-                    } else {
-                        Rectangle::with_center(Point::new(x, y), Size::new(*size, *size))
-                            .into_styled(PrimitiveStyle::with_fill(element))
-                            .draw(self)
-                            .unwrap();
+                CursorKind::Replace { size } => {
+                    self.replace_at(x, y, *size, ui.replace_source, element);
+                },
+                CursorKind::Text { size } => {
+                    // Only write once per click
+                    if ui.window_to_board_coordinate(prev_x, prev_y).is_none() {
+                        draw_text(self, &ui.text, Point::new(x, y), *size, element).unwrap();
                     }
                 },
+                // Gravity fields, rigid bodies, shapes, selections and snippets are placed by the main loop
+                CursorKind::GravityZone
+                | CursorKind::Attractor { .. }
+                | CursorKind::RigidBody
+                | CursorKind::Shape { .. }
+                | CursorKind::Select { .. }
+                | CursorKind::Paste => {},
+                CursorKind::Pen { size } => {
+                    self.draw_pen(pen, x, y, *size, element, &ui.brush, rng);
+                },
             }
         }
     }
+    /// A draw target painting into this world with the brush settings of the given UI
+    fn brush<'a>(&'a mut self, ui: &'a Ui, rng: &'a mut dyn RngCore) -> BrushTarget<'a> {
+        BrushTarget {
+            world: self,
+            settings: &ui.brush,
+            rng,
+        }
+    }
+    /// Draw the given shape through the given board coordinates, applying the given brush settings
+    pub fn draw_shape(
+        &mut self,
+        shape: &ShapeKind,
+        points: &[(i32, i32)],
+        element: Element,
+        settings: &BrushSettings,
+        rng: &mut dyn RngCore,
+    ) {
+        let points: Vec<Point> = points.iter().map(|&(x, y)| Point::new(x, y)).collect();
+        let mut brush = BrushTarget {
+            world: self,
+            settings,
+            rng,
+        };
+        shape.draw(&mut brush, &points, element).unwrap();
+    }
     /// Try to push a 'move down' to the moves vector and return true if that succeeded.
     fn move_down(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) -> bool {
         match self.board[x][y].density() {
@@ -288,7 +345,9 @@ impl GameWorld {
                 if let Some(b) = other_density {
                     let dens_q = b / a; // Density Quotient
                     if dens_q < 1. {
-                        if rng.random_bool(1. - dens_q as f64) {
+                        if rng
+                            .random_bool((1. - dens_q as f64) * self.settling_prob(x, y, x, y + 1))
+                        {
                             self.moves.push(Move::SwapElement {
                                 from_x: x,
                                 from_y: y,
@@ -454,7 +513,11 @@ impl GameWorld {
                 if let Some(b) = density_down_left {
                     let density_quot = b / a;
                     debug_assert!(density_quot < 1.);
-                    if rng.random_bool((1. - density_quot) as f64 * prob_quot) {
+                    if rng.random_bool(
+                        (1. - density_quot) as f64
+                            * prob_quot
+                            * self.settling_prob(x, y, x - 1, y + 1),
+                    ) {
                         // Skip move with side spread probability
                         if rng.random_bool(
                             self.board[x][y].spread_side_prob(&self.board[x - 1][y + 1]),
@@ -473,7 +536,11 @@ impl GameWorld {
                 if let Some(b) = density_down_right {
                     let density_quot = b / a;
                     debug_assert!(density_quot < 1.);
-                    if rng.random_bool((1. - density_quot) as f64 * prob_quot) {
+                    if rng.random_bool(
+                        (1. - density_quot) as f64
+                            * prob_quot
+                            * self.settling_prob(x, y, x + 1, y + 1),
+                    ) {
                         // Skip move with side spread probability
                         if rng.random_bool(
                             self.board[x][y].spread_side_prob(&self.board[x + 1][y + 1]),
@@ -493,34 +560,158 @@ impl GameWorld {
         }
         false
     }
+    /// Try to push a 'rise up' (buoyancy) to the moves vector and return true if that succeeded.
+    /// Powders that are less dense than the liquid above them swap places with it,
+    /// slowed down by the liquid drag.
+    fn rise_up(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) -> bool {
+        if y == 0 {
+            return false;
+        }
+        let ElementKind::Liquid {
+            density: liquid_density,
+        } = self.board[x][y - 1].kind()
+        else {
+            return false;
+        };
+        if let Some(my_density) = self.board[x][y].density()
+            && my_density < liquid_density
+        {
+            let dens_q = my_density / liquid_density;
+            if rng.random_bool((1. - dens_q as f64) * (1. - self.liquid_drag as f64)) {
+                self.moves.push(Move::SwapElement {
+                    from_x: x,
+                    from_y: y,
+                    to_x: x,
+                    to_y: y - 1,
+                });
+                return true;
+            }
+        }
+        false
+    }
     /// Try to push a 'move side' to the moves vector and return true if that succeeded.
+    /// Liquids move up to their dispersion distance to the side in one tick.
     fn move_side(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) -> bool {
-        let mut left = x > 0 && self.board[x - 1][y] == Element::None;
-        let mut right = x < (self.viewport_width() - 1) && self.board[x + 1][y] == Element::None;
-        if left && right {
-            left = rng.random_bool(0.5);
-            right = !left;
+        // Viscous liquids spread slower
+        if rng.random_bool(self.board[x][y].viscosity()) {
+            return false;
         }
-        if left {
-            self.moves.push(Move::MoveElement {
-                from_x: x,
-                from_y: y,
-                to_x: x - 1,
-                to_y: y,
-            });
-            return true;
+        let mut left = self.side_destination(x, y, -1);
+        let mut right = self.side_destination(x, y, 1);
+        // Surface tension keeps liquids from detaching from their droplet
+        if let Some(to_x) = left
+            && self.detaches(x, y, to_x, y)
+            && rng.random_bool(self.board[x][y].surface_tension())
+        {
+            left = None;
+        }
+        if let Some(to_x) = right
+            && self.detaches(x, y, to_x, y)
+            && rng.random_bool(self.board[x][y].surface_tension())
+        {
+            right = None;
+        }
+        if left.is_some() && right.is_some() {
+            if rng.random_bool(0.5) {
+                right = None;
+            } else {
+                left = None;
+            }
         }
-        if right {
+        if let Some(to_x) = left.or(right) {
             self.moves.push(Move::MoveElement {
                 from_x: x,
                 from_y: y,
-                to_x: x + 1,
+                to_x,
                 to_y: y,
             });
             return true;
         }
         false
     }
+    /// Find the farthest empty cell to the side (-1: left, 1: right) of the element at (x, y)
+    /// within its dispersion distance.
+    /// The search stops at the first occupied cell and at the first cell the element could fall from.
+    /// Since all moves are committed by destination, multiple elements targeting the same cell
+    /// are resolved like any other conflicting move.
+    fn side_destination(&self, x: usize, y: usize, direction: i32) -> Option<usize> {
+        let falls_down = self.board[x][y]
+            .density()
+            .is_some_and(|density| density > AIR_DENSITY);
+        let mut destination = None;
+        for distance in 1..=self.board[x][y].dispersion() as i32 {
+            let to_x = x as i32 + direction * distance;
+            if to_x < 0
+                || to_x >= self.viewport_width() as i32
+                || self.board[to_x as usize][y] != Element::None
+            {
+                break;
+            }
+            let to_x = to_x as usize;
+            destination = Some(to_x);
+            let can_fall = if falls_down {
+                y < self.viewport_height() - 1 && self.board[to_x][y + 1] == Element::None
+            } else {
+                y > 0 && self.board[to_x][y - 1] == Element::None
+            };
+            if can_fall {
+                break;
+            }
+        }
+        destination
+    }
+    /// Check if moving the element at (x, y) to the empty cell (to_x, to_y) detaches it from
+    /// all neighboring elements of the same kind that it is currently touching.
+    fn detaches(&self, x: usize, y: usize, to_x: usize, to_y: usize) -> bool {
+        let element = self.board[x][y];
+        let touches = |cx: usize, cy: usize| {
+            [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|(dx, dy)| {
+                let nx = cx as i32 + dx;
+                let ny = cy as i32 + dy;
+                nx >= 0
+                    && ny >= 0
+                    && nx < self.viewport_width() as i32
+                    && ny < self.viewport_height() as i32
+                    && (nx as usize, ny as usize) != (x, y)
+                    && discriminant(&self.board[nx as usize][ny as usize]) == discriminant(&element)
+            })
+        };
+        touches(x, y) && !touches(to_x, to_y)
+    }
+    /// The factor for the probability of a heavier element to sink through the element at the given
+    /// cell. Miscible elements rarely separate by density, so that diffusion can mix them.
+    fn settling_prob(&self, x: usize, y: usize, to_x: usize, to_y: usize) -> f64 {
+        match self.board[x][y].miscible_with(&self.board[to_x][to_y]) {
+            true => MISCIBLE_SETTLING_PROBABILITY,
+            false => 1.0,
+        }
+    }
+    /// Try to push a swap with a random adjacent miscible element to the moves vector
+    /// and return true if that succeeded. Lets miscible liquids and gases slowly mix.
+    fn diffuse(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) -> bool {
+        let element = self.board[x][y];
+        let (x_offs, y_offs) = *PROBES.choose(rng).unwrap();
+        let Some((to_x, to_y)) = self.offset(x, y, x_offs, y_offs) else {
+            return false;
+        };
+        let other = self.board[to_x][to_y];
+        if other == element || !element.miscible_with(&other) {
+            return false;
+        }
+        // Viscous liquids diffuse slower
+        let probability =
+            DIFFUSION_PROBABILITY * (1.0 - element.viscosity()) * (1.0 - other.viscosity());
+        if !rng.random_bool(probability) {
+            return false;
+        }
+        self.moves.push(Move::SwapElement {
+            from_x: x,
+            from_y: y,
+            to_x,
+            to_y,
+        });
+        true
+    }
     /// Tick (Calculate the next iteration of this board in-place)
     pub fn tick(&mut self, rng: &mut dyn RngCore) {
         self.moves.clear();
@@ -540,9 +731,47 @@ impl GameWorld {
             }
         }
 
+        // Then, let elements react according to the reaction table
+        for y in 0..height {
+            for x in 0..width {
+                self.react(x, y, rng);
+            }
+        }
+
+        // Let dissolved salt diffuse through water
+        for y in 0..height {
+            for x in 0..width {
+                self.diffuse_solutes(x, y, rng);
+            }
+        }
+
+        // Let fresh air flow in to replace the oxygen consumed by fires
+        self.diffuse_oxygen(rng);
+
+        // Let plants, vines and fungi grow
+        for y in 0..height {
+            for x in 0..width {
+                self.grow(x, y, rng);
+            }
+        }
+
+        // Let all creatures move
+        self.step_creatures(rng);
+
+        // Move all rigid bodies. They act like solids for all other elements.
+        self.step_rigid_bodies();
+
+        // Let black holes and white holes consume and emit elements
+        self.apply_field_sources(rng);
+
         // Then, collect and perform all moves:
         for y in 0..height {
             for x in 0..width {
+                // Gravity fields override the global gravity
+                if let Some(force) = self.field_force(x, y) {
+                    self.move_along(x, y, force, rng);
+                    continue;
+                }
                 // Gravity
                 if y != height - 1 {
                     match self.board[x][y].kind() {
@@ -552,7 +781,9 @@ impl GameWorld {
                             if !self.move_down(x, y, rng) {
                                 if !self.move_down_side(x, y, rng) {
                                     if !self.swap_down(x, y, rng) {
-                                        self.swap_down_side(x, y, rng);
+                                        if !self.swap_down_side(x, y, rng) {
+                                            self.rise_up(x, y, rng);
+                                        }
                                     }
                                 }
                             }
@@ -562,7 +793,9 @@ impl GameWorld {
                                 if !self.move_down_side(x, y, rng) {
                                     if !self.swap_down(x, y, rng) {
                                         if !self.swap_down_side(x, y, rng) {
-                                            self.move_side(x, y, rng);
+                                            if !self.move_side(x, y, rng) {
+                                                self.diffuse(x, y, rng);
+                                            }
                                         }
                                     }
                                 }
@@ -573,13 +806,30 @@ impl GameWorld {
                                 if !self.move_side(x, y, rng) {
                                     if !self.move_down_side(x, y, rng) {
                                         if !self.swap_down_side(x, y, rng) {
-                                            self.swap_down(x, y, rng);
+                                            if !self.swap_down(x, y, rng) {
+                                                self.diffuse(x, y, rng);
+                                            }
                                         }
                                     }
                                 }
                             }
                         },
                     }
+                } else {
+                    match self.board[x][y].kind() {
+                        ElementKind::None => {},
+                        ElementKind::Solid => {},
+                        // Powders on the bottom row can still rise up through denser liquids
+                        ElementKind::Powder { .. } => {
+                            self.rise_up(x, y, rng);
+                        },
+                        // Liquids and gases on the bottom row can still spread and mix
+                        ElementKind::Liquid { .. } | ElementKind::Gas { .. } => {
+                            if !self.move_side(x, y, rng) {
+                                self.diffuse(x, y, rng);
+                            }
+                        },
+                    }
                 }
             }
         }
@@ -663,6 +913,7 @@ impl GameWorld {
                         debug_assert_eq!(self.board[to_x][to_y], Element::None);
                         self.board[to_x][to_y] = self.board[from_x][from_y];
                         self.board[from_x][from_y] = Element::None;
+                        self.swap_oxygen((from_x, from_y), (to_x, to_y));
                     },
                     Move::SwapElement {
                         from_x,
@@ -670,11 +921,15 @@ impl GameWorld {
                         to_x,
                         to_y,
                     } => {
-                        debug_assert_ne!(self.board[from_x][from_y], Element::None);
-                        debug_assert_ne!(self.board[to_x][to_y], Element::None);
-                        let b = self.board[to_x][to_y];
-                        self.board[to_x][to_y] = self.board[from_x][from_y];
-                        self.board[from_x][from_y] = b;
+                        // Another move of this tick may already have emptied one of the cells
+                        if self.board[from_x][from_y] != Element::None
+                            && self.board[to_x][to_y] != Element::None
+                        {
+                            let b = self.board[to_x][to_y];
+                            self.board[to_x][to_y] = self.board[from_x][from_y];
+                            self.board[from_x][from_y] = b;
+                            self.swap_oxygen((from_x, from_y), (to_x, to_y));
+                        }
                     },
                 }
                 prev_i = i + 1;
@@ -687,23 +942,52 @@ impl GameWorld {
     pub fn new(width: usize, height: usize) -> Self {
         Self {
             board: vec![vec![Element::None; height]; width],
+            oxygen: vec![vec![MAX_OXYGEN; height]; width],
             moves: Vec::new(),
+            gravity_fields: Vec::new(),
+            rigid_bodies: Vec::new(),
+            liquid_drag: DEFAULT_LIQUID_DRAG,
+            population_history: VecDeque::new(),
+            population_timer: 0,
+            history: History::default(),
             width,
             height,
         }
     }
     pub fn reset(&mut self) {
         self.board = vec![vec![Element::None; self.height]; self.width];
+        self.oxygen = vec![vec![MAX_OXYGEN; self.height]; self.width];
+        self.mark_changed();
         self.moves.clear();
+        self.gravity_fields.clear();
+        self.rigid_bodies.clear();
+        self.population_history.clear();
+        self.population_timer = 0;
     }
     pub fn board(&self) -> &Vec<Vec<Element>> {
         &self.board
     }
+    /// The coordinates of the cell at the given offset, if it is inside the viewport
+    fn offset(&self, x: usize, y: usize, x_offs: i32, y_offs: i32) -> Option<(usize, usize)> {
+        let n_x = x as i32 + x_offs;
+        let n_y = y as i32 + y_offs;
+        if n_x < 0
+            || n_y < 0
+            || n_x >= self.viewport_width() as i32
+            || n_y >= self.viewport_height() as i32
+        {
+            return None;
+        }
+        Some((n_x as usize, n_y as usize))
+    }
+    pub fn liquid_drag_mut(&mut self) -> &mut f32 {
+        &mut self.liquid_drag
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::element::Element;
+    use crate::element::{Concentration, Element};
     use crate::world::GameWorld;
     use rand::SeedableRng;
     use rand_xorshift::XorShiftRng;
@@ -728,4 +1012,133 @@ mod tests {
         assert_eq!(board.board[1][1], Element::None);
         assert_eq!(board.board[1][2], Element::Sand);
     }
+    #[test]
+    fn test_dust_floats_up() {
+        let mut board = GameWorld::new(1, 6);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for y in 1..6 {
+            board.board[0][y] = Element::Water;
+        }
+        board.board[0][5] = Element::Dust;
+        for _ in 0..50 {
+            board.tick(&mut rng);
+        }
+        assert_eq!(board.board[0][1], Element::Dust);
+    }
+    #[test]
+    fn test_ash_floats_up() {
+        let mut board = GameWorld::new(1, 6);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for y in 1..6 {
+            board.board[0][y] = Element::Gasoline;
+        }
+        board.board[0][5] = Element::Ash;
+        for _ in 0..15 {
+            board.tick(&mut rng);
+        }
+        assert_eq!(board.board[0][1], Element::Ash);
+    }
+    #[test]
+    fn test_inviscid_liquid_spreads_every_tick() {
+        let mut board = GameWorld::new(41, 2);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for x in 0..41 {
+            board.board[x][1] = Element::BrickWall;
+        }
+        board.board[20][0] = Element::Gasoline;
+        let mut position = 20;
+        for _ in 0..10 {
+            board.tick(&mut rng);
+            let moved_to = (0..41).find(|x| board.board[*x][0] == Element::Gasoline);
+            assert_ne!(moved_to, Some(position));
+            position = moved_to.unwrap();
+        }
+    }
+    #[test]
+    fn test_viscous_liquid_rests_most_ticks() {
+        let mut board = GameWorld::new(41, 2);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for x in 0..41 {
+            board.board[x][1] = Element::BrickWall;
+        }
+        board.board[20][0] = Element::Honey;
+        let mut position = 20;
+        let mut moves = 0;
+        for _ in 0..100 {
+            board.tick(&mut rng);
+            let moved_to = (0..41).find(|x| board.board[*x][0] == Element::Honey);
+            if moved_to != Some(position) {
+                moves += 1;
+                position = moved_to.unwrap();
+            }
+        }
+        assert!(moves < 20);
+    }
+    #[test]
+    fn test_liquid_disperses_multiple_cells() {
+        let mut board = GameWorld::new(30, 2);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for x in 0..30 {
+            board.board[x][1] = Element::BrickWall;
+        }
+        board.board[0][0] = Element::Gasoline;
+        board.tick(&mut rng);
+        assert_eq!(
+            board.board[Element::Gasoline.dispersion()][0],
+            Element::Gasoline
+        );
+    }
+    #[test]
+    fn test_liquid_spreads_along_bottom_row() {
+        let mut board = GameWorld::new(30, 1);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[0][0] = Element::Gasoline;
+        board.tick(&mut rng);
+        assert_eq!(board.board[0][0], Element::None);
+        assert_eq!(
+            board.board[Element::Gasoline.dispersion()][0],
+            Element::Gasoline
+        );
+    }
+    #[test]
+    fn test_cryo_freezes_water_but_ice_not_salt_water() {
+        let mut board = GameWorld::new(3, 1);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[0][0] = Element::Cryo;
+        board.board[1][0] = Element::Water;
+        board.board[2][0] = Element::SaltWater {
+            concentration: Concentration::default(),
+        };
+        for _ in 0..200 {
+            board.tick(&mut rng);
+        }
+        assert_eq!(board.board[1][0], Element::Ice);
+        assert!(matches!(board.board[2][0], Element::SaltWater { .. }));
+    }
+    #[test]
+    fn test_miscible_gases_mix() {
+        let mut board = GameWorld::new(1, 10);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for y in 0..5 {
+            board.board[0][y] = Element::Hydrogen;
+            board.board[0][y + 5] = Element::Methane;
+        }
+        for _ in 0..2000 {
+            board.tick(&mut rng);
+        }
+        assert!((0..3).any(|y| board.board[0][y] == Element::Methane));
+    }
+    #[test]
+    fn test_immiscible_liquids_stay_separated() {
+        let mut board = GameWorld::new(1, 10);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for y in 0..5 {
+            board.board[0][y] = Element::Gasoline;
+            board.board[0][y + 5] = Element::Water;
+        }
+        for _ in 0..500 {
+            board.tick(&mut rng);
+        }
+        assert!((0..5).all(|y| board.board[0][y] == Element::Gasoline));
+    }
 }
@@ -0,0 +1,228 @@
+//   sandbox - World - oxygen and combustion
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::element::Element;
+use crate::ui::Ui;
+use crate::world::{GameWorld, PROBES};
+use rand::RngCore;
+use rand::prelude::IndexedRandom;
+
+/// The oxygen content of fresh air
+pub const MAX_OXYGEN: u8 = 100;
+/// The oxygen a flame or burning particle consumes in one tick
+const OXYGEN_CONSUMPTION: u8 = 5;
+
+/// The oxygen content a cell gets when the given element is placed into it
+pub(in crate::world) const fn initial_oxygen(element: &Element) -> u8 {
+    match element {
+        Element::None | Element::Oxygen => MAX_OXYGEN,
+        _ => 0,
+    }
+}
+
+impl GameWorld {
+    pub fn get_oxygen_at(&self, ui: &Ui, window_x: i32, window_y: i32) -> Option<u8> {
+        ui.window_to_board_coordinate(window_x, window_y)
+            .map(|(x, y)| self.oxygen[x as usize][y as usize])
+    }
+    /// Replace the element at the given cell, resetting the oxygen content of the cell.
+    /// This is meant for changes made by the user, which are recorded in the undo history.
+    pub(in crate::world) fn set_element(&mut self, x: usize, y: usize, element: Element) {
+        if self.board[x][y] == element && self.oxygen[x][y] == initial_oxygen(&element) {
+            return;
+        }
+        self.mark_changed();
+        self.board[x][y] = element;
+        self.oxygen[x][y] = initial_oxygen(&element);
+    }
+    /// Replace the element at the given cell as part of the simulation.
+    /// Elements displace the oxygen of their cell, but a cell that is emptied keeps the oxygen
+    /// that is left in it, so burnt out fires do not leave fresh air behind.
+    pub(in crate::world) fn replace_element(&mut self, x: usize, y: usize, element: Element) {
+        self.board[x][y] = element;
+        if element != Element::None {
+            self.oxygen[x][y] = initial_oxygen(&element);
+        }
+    }
+    /// Swap the oxygen content of the given cells, i.e., when the elements of the cells are swapped
+    pub(in crate::world) fn swap_oxygen(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let oxygen = self.oxygen[a.0][a.1];
+        self.oxygen[a.0][a.1] = self.oxygen[b.0][b.1];
+        self.oxygen[b.0][b.1] = oxygen;
+    }
+    /// The cells a burning element at the given position can draw oxygen from, including itself
+    fn oxygen_sources(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::once((x, y)).chain(
+            PROBES
+                .iter()
+                .filter_map(move |&(x_offs, y_offs)| self.offset(x, y, x_offs, y_offs))
+                .filter(|&(n_x, n_y)| {
+                    matches!(self.board[n_x][n_y], Element::None | Element::Oxygen)
+                }),
+        )
+    }
+    /// Whether there is enough oxygen around the given cell to keep a fire burning
+    pub(in crate::world) fn can_burn(&self, x: usize, y: usize) -> bool {
+        self.oxygen_sources(x, y)
+            .map(|(s_x, s_y)| self.oxygen[s_x][s_y] as u32)
+            .sum::<u32>()
+            >= OXYGEN_CONSUMPTION as u32
+    }
+    /// Let a burning element consume oxygen from its own cell first and then from the adjacent air
+    pub(in crate::world) fn consume_oxygen(&mut self, x: usize, y: usize) {
+        let mut needed = OXYGEN_CONSUMPTION;
+        let sources: Vec<(usize, usize)> = self.oxygen_sources(x, y).collect();
+        for (s_x, s_y) in sources {
+            let consumed = needed.min(self.oxygen[s_x][s_y]);
+            self.oxygen[s_x][s_y] -= consumed;
+            needed -= consumed;
+            if needed == 0 {
+                break;
+            }
+        }
+    }
+    /// Let oxygen diffuse through the air.
+    /// The world border and oxygen gas act as a source of fresh air.
+    pub(in crate::world) fn diffuse_oxygen(&mut self, rng: &mut dyn RngCore) {
+        let width = self.viewport_width();
+        let height = self.viewport_height();
+        for y in 0..height {
+            for x in 0..width {
+                if self.board[x][y] != Element::None {
+                    continue;
+                }
+                if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                    self.oxygen[x][y] = MAX_OXYGEN;
+                }
+                let (x_offs, y_offs) = *PROBES.choose(rng).unwrap();
+                let Some((n_x, n_y)) = self.offset(x, y, x_offs, y_offs) else {
+                    continue;
+                };
+                match self.board[n_x][n_y] {
+                    Element::None => {
+                        let mean =
+                            ((self.oxygen[x][y] as u16 + self.oxygen[n_x][n_y] as u16) / 2) as u8;
+                        self.oxygen[x][y] = mean;
+                        self.oxygen[n_x][n_y] = mean;
+                    },
+                    Element::Oxygen => self.oxygen[x][y] = MAX_OXYGEN,
+                    _ => {},
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::{Element, Flammability};
+    use crate::world::GameWorld;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_sealed_chamber_snuffs_out_fire() {
+        let mut board = GameWorld::new(7, 7);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for i in 0..7 {
+            board.set_element(i, 0, Element::BrickWall);
+            board.set_element(i, 6, Element::BrickWall);
+            board.set_element(0, i, Element::BrickWall);
+            board.set_element(6, i, Element::BrickWall);
+        }
+        for x in 1..6 {
+            for y in 3..6 {
+                board.set_element(x, y, Element::Gasoline);
+            }
+        }
+        board.set_element(3, 2, Element::Flame);
+        for _ in 0..3000 {
+            board.tick(&mut rng);
+        }
+        assert!(
+            board
+                .board
+                .iter()
+                .flatten()
+                .all(|e| !matches!(e, Element::Flame | Element::BurningParticle { .. }))
+        );
+        assert!(
+            board
+                .board
+                .iter()
+                .flatten()
+                .any(|e| *e == Element::Gasoline)
+        );
+    }
+    #[test]
+    fn test_oxygen_lets_starved_fires_flare_up() {
+        let mut board = GameWorld::new(9, 9);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for i in 0..9 {
+            board.set_element(i, 0, Element::BrickWall);
+            board.set_element(i, 8, Element::BrickWall);
+            board.set_element(0, i, Element::BrickWall);
+            board.set_element(8, i, Element::BrickWall);
+        }
+        for x in 1..8 {
+            board.set_element(x, 7, Element::Wood);
+            for y in 1..7 {
+                board.oxygen[x][y] = 0;
+            }
+        }
+        let Flammability::Flammable {
+            decay_prob,
+            flame_spawn_prob,
+            products,
+            ..
+        } = Element::Wood.flammability()
+        else {
+            panic!("Wood is not flammable");
+        };
+        board.set_element(
+            4,
+            7,
+            Element::BurningParticle {
+                burned_element_kind: Element::Wood.kind(),
+                decay_prob,
+                flame_spawn_prob,
+                products,
+            },
+        );
+        let fire = |board: &GameWorld| {
+            board
+                .board
+                .iter()
+                .flatten()
+                .filter(|e| matches!(e, Element::Flame | Element::BurningParticle { .. }))
+                .count()
+        };
+        // Without oxygen, the fire smolders without spreading
+        for _ in 0..100 {
+            board.tick(&mut rng);
+            assert_eq!(fire(&board), 1);
+        }
+        board.set_element(4, 2, Element::Oxygen);
+        let mut max_fire = 0;
+        for _ in 0..300 {
+            board.tick(&mut rng);
+            max_fire = max_fire.max(fire(&board));
+        }
+        assert!(max_fire > 1);
+        // The oxygen itself does not burn
+        assert!(board.board.iter().flatten().any(|e| *e == Element::Oxygen));
+    }
+}
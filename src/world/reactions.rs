@@ -0,0 +1,310 @@
+//   sandbox - World - chemical reactions
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::element::{Concentration, Element, ROOM_TEMPERATURE, SEAWATER_CONCENTRATION};
+use crate::world::{GameWorld, PROBES};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+/// A chemical reaction between two adjacent elements
+pub struct Reaction {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The two elements that have to be adjacent to react
+    pub reactants: (Element, Element),
+    /// An element that has to be adjacent to one of the reactants. It is not used up by the reaction.
+    pub catalyst: Option<Element>,
+    /// The minimum temperature in °C of the reactants or any element adjacent to them
+    pub min_temperature: Option<f32>,
+    /// The probability of the reaction to occur in one tick if all conditions are met
+    pub probability: f64,
+    /// The elements replacing the first and second reactant
+    pub products: (Option<Element>, Option<Element>),
+    /// Additional products that are placed into empty cells adjacent to the reactants
+    pub byproducts: &'static [Element],
+    /// The released energy. Exothermic reactions (positive energy) spawn this many flames into adjacent
+    /// empty cells, endothermic reactions (negative energy) freeze this many adjacent water cells.
+    pub energy: i8,
+}
+
+/// All reactions, in the order they are tried
+pub const REACTIONS: &[Reaction] = &[
+    Reaction {
+        name: "Neutralization",
+        description: "Acid and lye neutralize each other to salt water, releasing steam.",
+        reactants: (Element::Acid, Element::Lye),
+        catalyst: None,
+        min_temperature: None,
+        probability: 0.2,
+        products: (
+            Some(Element::SaltWater {
+                concentration: Concentration(SEAWATER_CONCENTRATION),
+            }),
+            None,
+        ),
+        byproducts: &[Element::Steam],
+        energy: 0,
+    },
+    Reaction {
+        name: "Oxyhydrogen",
+        description: "Hydrogen and oxygen explode into steam when heated.",
+        reactants: (Element::Hydrogen, Element::Oxygen),
+        catalyst: None,
+        min_temperature: Some(500.0),
+        probability: 0.9,
+        products: (Some(Element::Steam), Some(Element::Steam)),
+        byproducts: &[],
+        energy: 2,
+    },
+    Reaction {
+        name: "Steam Reforming",
+        description: "Methane and steam form hydrogen at very high temperatures.",
+        reactants: (Element::Methane, Element::Steam),
+        catalyst: None,
+        min_temperature: Some(700.0),
+        probability: 0.1,
+        products: (Some(Element::Hydrogen), Some(Element::Hydrogen)),
+        byproducts: &[Element::Hydrogen],
+        energy: -1,
+    },
+    Reaction {
+        name: "Fermentation",
+        description: "Fungi ferment honey dissolved in water, releasing methane.",
+        reactants: (Element::Honey, Element::Water),
+        catalyst: Some(Element::Fungus),
+        min_temperature: None,
+        probability: 0.01,
+        products: (None, Some(Element::Water)),
+        byproducts: &[Element::Methane],
+        energy: 0,
+    },
+    Reaction {
+        name: "Thawing Salt",
+        description: "Salt melts ice to salt water, cooling down its surroundings.",
+        reactants: (Element::Salt, Element::Ice),
+        catalyst: None,
+        min_temperature: None,
+        probability: 0.05,
+        products: (
+            None,
+            Some(Element::SaltWater {
+                concentration: Concentration(SEAWATER_CONCENTRATION),
+            }),
+        ),
+        byproducts: &[],
+        energy: -1,
+    },
+];
+
+impl Reaction {
+    /// The reaction formula, e.g., "Acid + Lye → Saltwater + Steam"
+    pub fn formula(&self) -> String {
+        let reactants = format!("{} + {}", self.reactants.0, self.reactants.1);
+        let products = [self.products.0, self.products.1]
+            .into_iter()
+            .flatten()
+            .chain(self.byproducts.iter().copied())
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+        match products.is_empty() {
+            true => format!("{} → nothing", reactants),
+            false => format!("{} → {}", reactants, products.join(" + ")),
+        }
+    }
+}
+
+impl GameWorld {
+    /// The adjacent cells of the given cell that are inside the viewport
+    fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        PROBES
+            .iter()
+            .filter_map(move |&(x_offs, y_offs)| self.offset(x, y, x_offs, y_offs))
+    }
+    /// Check if all conditions of the given reaction are met for the reactants at the given cells
+    fn can_react(&self, reaction: &Reaction, a: (usize, usize), b: (usize, usize)) -> bool {
+        let surroundings = || {
+            [a, b]
+                .into_iter()
+                .chain(self.neighbors(a.0, a.1))
+                .chain(self.neighbors(b.0, b.1))
+                .map(|(x, y)| self.board[x][y])
+        };
+        if let Some(catalyst) = reaction.catalyst
+            && !surroundings().any(|e| e == catalyst)
+        {
+            return false;
+        }
+        if let Some(min_temperature) = reaction.min_temperature
+            && !surroundings()
+                .any(|e| e.temperature().unwrap_or(ROOM_TEMPERATURE) >= min_temperature)
+        {
+            return false;
+        }
+        true
+    }
+    /// Let the element at the given cell react with an adjacent element according to the reaction table
+    pub(in crate::world) fn react(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) {
+        for reaction in REACTIONS
+            .iter()
+            .filter(|r| r.reactants.0 == self.board[x][y])
+        {
+            let partner = self
+                .neighbors(x, y)
+                .find(|&(b_x, b_y)| self.board[b_x][b_y] == reaction.reactants.1);
+            let Some(b) = partner else {
+                continue;
+            };
+            if !self.can_react(reaction, (x, y), b) || !rng.random_bool(reaction.probability) {
+                continue;
+            }
+            self.replace_element(x, y, reaction.products.0.unwrap_or(Element::None));
+            self.replace_element(b.0, b.1, reaction.products.1.unwrap_or(Element::None));
+            // Place the byproducts and released flames into the surrounding empty cells
+            let mut empty: Vec<(usize, usize)> = self
+                .neighbors(x, y)
+                .chain(self.neighbors(b.0, b.1))
+                .filter(|&(e_x, e_y)| self.board[e_x][e_y] == Element::None)
+                .collect();
+            empty.sort_unstable();
+            empty.dedup();
+            empty.shuffle(rng);
+            let released = [Element::Flame].repeat(reaction.energy.max(0) as usize);
+            for (&(e_x, e_y), &product) in empty
+                .iter()
+                .zip(reaction.byproducts.iter().chain(released.iter()))
+            {
+                self.replace_element(e_x, e_y, product);
+            }
+            // Endothermic reactions freeze the surrounding water
+            let frozen: Vec<(usize, usize)> = self
+                .neighbors(x, y)
+                .chain(self.neighbors(b.0, b.1))
+                .filter(|&(w_x, w_y)| self.board[w_x][w_y] == Element::Water)
+                .take(reaction.energy.min(0).unsigned_abs() as usize)
+                .collect();
+            for (w_x, w_y) in frozen {
+                self.replace_element(w_x, w_y, Element::Ice);
+            }
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::world::GameWorld;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_honey_ferments_with_catalyst() {
+        let mut board = GameWorld::new(3, 1);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[0][0] = Element::Honey;
+        board.board[1][0] = Element::Water;
+        board.board[2][0] = Element::Fungus;
+        for _ in 0..1000 {
+            board.react(0, 0, &mut rng);
+        }
+        assert_ne!(board.board[0][0], Element::Honey);
+    }
+    #[test]
+    fn test_honey_does_not_ferment_without_catalyst() {
+        let mut board = GameWorld::new(3, 1);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[0][0] = Element::Honey;
+        board.board[1][0] = Element::Water;
+        board.board[2][0] = Element::Wood;
+        for _ in 0..1000 {
+            board.react(0, 0, &mut rng);
+        }
+        assert_eq!(board.board[0][0], Element::Honey);
+    }
+    /// The number of cells with the given element
+    fn count(board: &GameWorld, element: Element) -> usize {
+        board
+            .board
+            .iter()
+            .flatten()
+            .filter(|&&e| e == element)
+            .count()
+    }
+    /// Let the element at the given cell react until it has been replaced
+    fn react_until_replaced(board: &mut GameWorld, x: usize, y: usize) {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let element = board.board[x][y];
+        for _ in 0..1000 {
+            board.react(x, y, &mut rng);
+            if board.board[x][y] != element {
+                return;
+            }
+        }
+        panic!("{} did not react", element);
+    }
+    #[test]
+    fn test_reactions_require_the_minimum_temperature() {
+        let mut board = GameWorld::new(3, 1);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[0][0] = Element::Hydrogen;
+        board.board[1][0] = Element::Oxygen;
+        for _ in 0..1000 {
+            board.react(0, 0, &mut rng);
+        }
+        assert_eq!(board.board[0][0], Element::Hydrogen);
+        // Lava next to one of the reactants is hot enough
+        board.board[2][0] = Element::Lava;
+        react_until_replaced(&mut board, 0, 0);
+        assert_eq!(board.board[0][0], Element::Steam);
+        assert_eq!(board.board[1][0], Element::Steam);
+    }
+    #[test]
+    fn test_byproducts_are_placed_into_empty_neighbors() {
+        let mut board = GameWorld::new(4, 1);
+        board.board[0][0] = Element::Acid;
+        board.board[1][0] = Element::Lye;
+        board.board[2][0] = Element::Stone;
+        react_until_replaced(&mut board, 0, 0);
+        assert!(matches!(board.board[0][0], Element::SaltWater { .. }));
+        // The lye is used up and its cell is the only empty cell next to the reactants
+        assert_eq!(board.board[1][0], Element::Steam);
+        assert_eq!(board.board[2][0], Element::Stone);
+        assert_eq!(board.board[3][0], Element::None);
+    }
+    #[test]
+    fn test_exothermic_reactions_spawn_flames() {
+        let mut board = GameWorld::new(5, 3);
+        board.board[1][1] = Element::Hydrogen;
+        board.board[2][1] = Element::Oxygen;
+        board.board[3][1] = Element::Lava;
+        react_until_replaced(&mut board, 1, 1);
+        assert_eq!(count(&board, Element::Flame), 2);
+        assert_eq!(count(&board, Element::Steam), 2);
+    }
+    #[test]
+    fn test_endothermic_reactions_freeze_water() {
+        let mut board = GameWorld::new(4, 3);
+        board.board[1][1] = Element::Salt;
+        board.board[2][1] = Element::Ice;
+        for (x, y) in [(1, 0), (1, 2), (2, 0)] {
+            board.board[x][y] = Element::Water;
+        }
+        react_until_replaced(&mut board, 1, 1);
+        assert!(matches!(board.board[2][1], Element::SaltWater { .. }));
+        assert_eq!(count(&board, Element::Ice), 1);
+        assert_eq!(count(&board, Element::Water), 2);
+    }
+}
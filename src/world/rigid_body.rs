@@ -0,0 +1,739 @@
+//   sandbox - World - rigid bodies
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::codec::{decode_element, encode_element};
+use crate::element::{Element, ElementKind};
+use crate::world::world_file::parse_field;
+use crate::world::{GameWorld, PROBES};
+use std::collections::{HashSet, VecDeque};
+
+/// The gravitational acceleration of rigid bodies in cells per tick²
+const RIGID_BODY_GRAVITY: f32 = 0.15;
+/// The maximum speed of rigid bodies in cells per tick
+const RIGID_BODY_MAX_SPEED: f32 = 6.0;
+/// The fraction of the velocity that is kept (in opposite direction) when bouncing off an obstacle
+const RESTITUTION: f32 = 0.2;
+/// The fraction of the velocity parallel to a surface that is kept when sliding along it
+const FRICTION: f32 = 0.8;
+/// The fraction of the angular velocity that is kept in each tick
+const ANGULAR_DAMPING: f32 = 0.97;
+/// How strongly off-center impacts make a body rotate
+const ROTATION_RESPONSE: f32 = 0.004;
+
+/// A solid object made up of cells that moves, rotates and collides as a whole
+#[derive(Clone, Debug, PartialEq)]
+pub struct RigidBody {
+    /// The shape of this body in local coordinates, indexed by \[x\]\[y\]
+    cells: Vec<Vec<Option<Element>>>,
+    /// The center of mass in local coordinates
+    center: (f32, f32),
+    /// The position of the center of mass on the board
+    position: (f32, f32),
+    /// The velocity in cells per tick
+    velocity: (f32, f32),
+    /// The rotation angle in radians (clockwise)
+    angle: f32,
+    /// The angular velocity in radians per tick
+    angular_velocity: f32,
+    /// All board cells currently showing this body, as (board x, board y, local x, local y)
+    occupied: Vec<(usize, usize, usize, usize)>,
+}
+
+impl RigidBody {
+    /// Create a rigid body from the given board cells
+    fn from_cells(cells: &[(usize, usize, Element)]) -> Self {
+        let min_x = cells.iter().map(|c| c.0).min().unwrap_or(0);
+        let min_y = cells.iter().map(|c| c.1).min().unwrap_or(0);
+        let width = cells.iter().map(|c| c.0 - min_x + 1).max().unwrap_or(0);
+        let height = cells.iter().map(|c| c.1 - min_y + 1).max().unwrap_or(0);
+        let mut local = vec![vec![None; height]; width];
+        let mut occupied = Vec::with_capacity(cells.len());
+        for (x, y, e) in cells {
+            local[x - min_x][y - min_y] = Some(*e);
+            occupied.push((*x, *y, x - min_x, y - min_y));
+        }
+        let mut ret = Self {
+            cells: local,
+            center: (0.0, 0.0),
+            position: (0.0, 0.0),
+            velocity: (0.0, 0.0),
+            angle: 0.0,
+            angular_velocity: 0.0,
+            occupied,
+        };
+        ret.center = ret.center_of_mass();
+        ret.position = (min_x as f32 + ret.center.0, min_y as f32 + ret.center.1);
+        ret
+    }
+    /// Encode this body as one line of whitespace-separated fields: the position, velocity, angle
+    /// and angular velocity, the size of the local cell grid, all local cells column by column
+    /// and all board cells currently showing this body
+    pub(in crate::world) fn encode(&self) -> String {
+        let cells: Vec<String> = self
+            .cells
+            .iter()
+            .flatten()
+            .map(|cell| match cell {
+                Some(element) => encode_element(element),
+                None => "-".to_string(),
+            })
+            .collect();
+        let occupied: Vec<String> = self
+            .occupied
+            .iter()
+            .map(|(bx, by, lx, ly)| format!("{}:{}:{}:{}", bx, by, lx, ly))
+            .collect();
+        format!(
+            "{} {} {} {} {} {} {} {} {} {}",
+            self.position.0,
+            self.position.1,
+            self.velocity.0,
+            self.velocity.1,
+            self.angle,
+            self.angular_velocity,
+            self.cells.len(),
+            self.cells.first().map_or(0, Vec::len),
+            cells.join(","),
+            occupied.join(",")
+        )
+    }
+    /// Decode a body encoded by [RigidBody::encode] that lies inside a world of the given size
+    pub(in crate::world) fn decode(
+        encoded: &str,
+        width: usize,
+        height: usize,
+    ) -> Result<Self, String> {
+        let mut tokens = encoded.split_whitespace();
+        let mut float = || -> Result<f32, String> {
+            let value: f32 = parse_field(tokens.next())?;
+            value
+                .is_finite()
+                .then_some(value)
+                .ok_or_else(|| "Invalid rigid body".to_string())
+        };
+        let position = (float()?, float()?);
+        let velocity = (float()?, float()?);
+        let angle = float()?;
+        let angular_velocity = float()?;
+        let local_width: usize = parse_field(tokens.next())?;
+        let local_height: usize = parse_field(tokens.next())?;
+        let cells: Vec<Option<Element>> = tokens
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(|cell| match cell {
+                "-" => Ok(None),
+                encoded => decode_element(encoded).map(Some),
+            })
+            .collect::<Result<_, _>>()?;
+        if local_width == 0 || local_height == 0 || cells.len() != local_width * local_height {
+            return Err("Invalid rigid body shape".to_string());
+        }
+        let occupied: Vec<(usize, usize, usize, usize)> = tokens
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(|cell| {
+                let mut coordinates = cell.split(':');
+                Ok((
+                    parse_field(coordinates.next())?,
+                    parse_field(coordinates.next())?,
+                    parse_field(coordinates.next())?,
+                    parse_field(coordinates.next())?,
+                ))
+            })
+            .collect::<Result<_, String>>()?;
+        if tokens.next().is_some() {
+            return Err("Invalid rigid body".to_string());
+        }
+        let mut body = Self {
+            cells: cells
+                .chunks(local_height)
+                .map(|column| column.to_vec())
+                .collect(),
+            center: (0.0, 0.0),
+            position,
+            velocity,
+            angle,
+            angular_velocity,
+            occupied,
+        };
+        body.center = body.center_of_mass();
+        let inside = |x: i32, y: i32| x >= 0 && y >= 0 && x < width as i32 && y < height as i32;
+        if body.occupied.iter().any(|&(bx, by, lx, ly)| {
+            !inside(bx as i32, by as i32) || lx >= local_width || ly >= local_height
+        }) || body
+            .rasterize(position, angle)
+            .iter()
+            .any(|&(bx, by, _, _)| !inside(bx, by))
+        {
+            return Err("Rigid body outside of the world".to_string());
+        }
+        Ok(body)
+    }
+    /// Iterate over all local cells of this body with their element
+    fn local_cells(&self) -> impl Iterator<Item = (usize, usize, Element)> + '_ {
+        self.cells.iter().enumerate().flat_map(|(x, col)| {
+            col.iter()
+                .enumerate()
+                .filter_map(move |(y, e)| e.map(|e| (x, y, e)))
+        })
+    }
+    fn mass(&self) -> f32 {
+        self.local_cells()
+            .map(|(_, _, e)| e.rigid_density().unwrap_or(0.0))
+            .sum()
+    }
+    fn center_of_mass(&self) -> (f32, f32) {
+        let mass = self.mass().max(f32::EPSILON);
+        let (mut cx, mut cy) = (0.0, 0.0);
+        for (x, y, e) in self.local_cells() {
+            let m = e.rigid_density().unwrap_or(0.0);
+            cx += (x as f32 + 0.5) * m;
+            cy += (y as f32 + 0.5) * m;
+        }
+        (cx / mass, cy / mass)
+    }
+    /// The moment of inertia around the center of mass
+    fn inertia(&self) -> f32 {
+        self.local_cells()
+            .map(|(x, y, e)| {
+                let dx = x as f32 + 0.5 - self.center.0;
+                let dy = y as f32 + 0.5 - self.center.1;
+                e.rigid_density().unwrap_or(0.0) * (dx * dx + dy * dy + 1.0)
+            })
+            .sum()
+    }
+    /// The maximum distance of any cell corner from the center of mass
+    fn radius(&self) -> f32 {
+        self.local_cells()
+            .map(|(x, y, _)| {
+                let dx = (x as f32 + 0.5 - self.center.0).abs() + 0.5;
+                let dy = (y as f32 + 0.5 - self.center.1).abs() + 0.5;
+                (dx * dx + dy * dy).sqrt()
+            })
+            .fold(0.0, f32::max)
+    }
+    /// The strength of this body, i.e., the impact speed at which it breaks apart
+    fn break_speed(&self) -> f32 {
+        self.local_cells()
+            .filter_map(|(_, _, e)| e.impact_strength())
+            .fold(f32::INFINITY, f32::min)
+    }
+    /// Calculate the board cells covered by this body at the given pose,
+    /// as (board x, board y, local x, local y).
+    /// Every local cell is mapped to exactly one board cell, and vice versa.
+    fn rasterize(&self, position: (f32, f32), angle: f32) -> Vec<(i32, i32, usize, usize)> {
+        let (sin, cos) = angle.sin_cos();
+        let mut taken = HashSet::new();
+        let mut ret = Vec::new();
+        for (lx, ly, _) in self.local_cells() {
+            let dx = lx as f32 + 0.5 - self.center.0;
+            let dy = ly as f32 + 0.5 - self.center.1;
+            // Rotate into board coordinates
+            let x = cos * dx - sin * dy + position.0;
+            let y = sin * dx + cos * dy + position.1;
+            let distance =
+                |&(bx, by): &(i32, i32)| (bx as f32 + 0.5 - x).hypot(by as f32 + 0.5 - y);
+            // At some angles, two local cells fall into the same board cell.
+            // The later one then takes the nearest board cell that is still free.
+            let (bx, by) = (x.floor() as i32, y.floor() as i32);
+            let mut r = 0;
+            let cell = loop {
+                let free = (-r..=r)
+                    .flat_map(|ox| (-r..=r).map(move |oy| (bx + ox, by + oy)))
+                    .filter(|cell| !taken.contains(cell))
+                    .min_by(|a, b| distance(a).total_cmp(&distance(b)));
+                if let Some(cell) = free {
+                    break cell;
+                }
+                r += 1;
+            };
+            taken.insert(cell);
+            ret.push((cell.0, cell.1, lx, ly));
+        }
+        ret
+    }
+}
+
+impl GameWorld {
+    pub fn rigid_bodies(&self) -> &Vec<RigidBody> {
+        &self.rigid_bodies
+    }
+    /// Turn all connected groups of rigid solids inside the given rectangle into rigid bodies
+    pub fn create_rigid_bodies(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
+        let x2 = x2.min(self.viewport_width() - 1);
+        let y2 = y2.min(self.viewport_height() - 1);
+        let already_bodies: HashSet<(usize, usize)> = self
+            .rigid_bodies
+            .iter()
+            .flat_map(|b| b.occupied.iter().map(|&(x, y, _, _)| (x, y)))
+            .collect();
+        let mut visited = HashSet::new();
+        for x in x1..=x2 {
+            for y in y1..=y2 {
+                if visited.contains(&(x, y))
+                    || already_bodies.contains(&(x, y))
+                    || self.board[x][y].rigid_density().is_none()
+                {
+                    continue;
+                }
+                // Collect the connected group of rigid cells inside the rectangle
+                let mut group = Vec::new();
+                let mut stack = vec![(x, y)];
+                visited.insert((x, y));
+                while let Some((cx, cy)) = stack.pop() {
+                    group.push((cx, cy, self.board[cx][cy]));
+                    for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                        let nx = cx as i32 + dx;
+                        let ny = cy as i32 + dy;
+                        if nx < x1 as i32 || ny < y1 as i32 || nx > x2 as i32 || ny > y2 as i32 {
+                            continue;
+                        }
+                        let (nx, ny) = (nx as usize, ny as usize);
+                        if !visited.contains(&(nx, ny))
+                            && !already_bodies.contains(&(nx, ny))
+                            && self.board[nx][ny].rigid_density().is_some()
+                        {
+                            visited.insert((nx, ny));
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+                self.mark_changed();
+                self.rigid_bodies.push(RigidBody::from_cells(&group));
+            }
+        }
+    }
+    /// Simulate all rigid bodies for one tick
+    pub(in crate::world) fn step_rigid_bodies(&mut self) {
+        let mut bodies = std::mem::take(&mut self.rigid_bodies);
+        bodies.retain_mut(|body| self.step_rigid_body(body));
+        self.rigid_bodies = bodies;
+    }
+    /// Simulate one rigid body. Returns false if the body ceased to exist.
+    fn step_rigid_body(&mut self, body: &mut RigidBody) -> bool {
+        // Remove all cells that have been destroyed or replaced since the last tick, e.g., by fire
+        for &(bx, by, lx, ly) in &body.occupied {
+            if body.cells[lx][ly] != Some(self.board[bx][by]) {
+                body.cells[lx][ly] = None;
+            }
+        }
+        body.occupied
+            .retain(|&(_, _, lx, ly)| body.cells[lx][ly].is_some());
+        if body.occupied.is_empty() {
+            return false;
+        }
+        let (buoyancy, torque, submerged) = self.buoyancy_of(body);
+        let previous = (body.occupied.clone(), body.position, body.angle);
+        for &(bx, by, _, _) in &body.occupied {
+            self.replace_element(bx, by, Element::None);
+        }
+        // Apply gravity and buoyancy
+        let mass = body.mass().max(f32::EPSILON);
+        body.velocity.1 += RIGID_BODY_GRAVITY * (mass - buoyancy) / mass;
+        body.angular_velocity += RIGID_BODY_GRAVITY * torque / body.inertia().max(f32::EPSILON);
+        // A fully submerged body loses the liquid drag fraction of its velocity per tick
+        let drag = 1.0 - self.liquid_drag * submerged;
+        body.velocity = (body.velocity.0 * drag, body.velocity.1 * drag);
+        body.angular_velocity *= drag * ANGULAR_DAMPING;
+        let speed = body.velocity.0.hypot(body.velocity.1);
+        if speed > RIGID_BODY_MAX_SPEED {
+            body.velocity.0 *= RIGID_BODY_MAX_SPEED / speed;
+            body.velocity.1 *= RIGID_BODY_MAX_SPEED / speed;
+        }
+        // Move in sub-steps of at most one cell
+        let radius = body.radius();
+        let steps = body
+            .velocity
+            .0
+            .abs()
+            .max(body.velocity.1.abs())
+            .max(body.angular_velocity.abs() * radius)
+            .ceil()
+            .max(1.0) as usize;
+        for _ in 0..steps {
+            let next_pos = (
+                body.position.0 + body.velocity.0 / steps as f32,
+                body.position.1 + body.velocity.1 / steps as f32,
+            );
+            let next_angle = body.angle + body.angular_velocity / steps as f32;
+            if self.fits(body, next_pos, next_angle) {
+                body.position = next_pos;
+                body.angle = next_angle;
+                continue;
+            }
+            if self.fits(body, next_pos, body.angle) {
+                // Rotation is blocked, but translation is not
+                body.position = next_pos;
+                body.angular_velocity *= -RESTITUTION;
+                continue;
+            }
+            // We hit an obstacle - check if we break apart
+            let impact_speed = body.velocity.0.hypot(body.velocity.1);
+            if impact_speed > body.break_speed() {
+                // Break apart into particles
+                if !self.stamp(body, true) {
+                    self.draw_body(body, previous.0, true);
+                }
+                return false;
+            }
+            // Off-center impacts make the body rotate
+            let blocked = self.blocked_cells(body, next_pos, body.angle);
+            if !blocked.is_empty() {
+                let contact_x =
+                    blocked.iter().map(|c| c.0 as f32 + 0.5).sum::<f32>() / blocked.len() as f32;
+                body.angular_velocity +=
+                    ROTATION_RESPONSE * (body.position.0 - contact_x) * body.velocity.1;
+            }
+            // Try to slide along the obstacle
+            let slide_y = (body.position.0, next_pos.1);
+            let slide_x = (next_pos.0, body.position.1);
+            if body.velocity.1 != 0.0 && self.fits(body, slide_y, body.angle) {
+                body.position = slide_y;
+                body.velocity.0 *= -RESTITUTION;
+                body.velocity.1 *= FRICTION;
+            } else if body.velocity.0 != 0.0 && self.fits(body, slide_x, body.angle) {
+                body.position = slide_x;
+                body.velocity.0 *= FRICTION;
+                body.velocity.1 *= -RESTITUTION;
+            } else {
+                body.velocity.0 *= -RESTITUTION;
+                body.velocity.1 *= -RESTITUTION;
+            }
+            break;
+        }
+        if !self.stamp(body, false) {
+            // The liquids and gases in the way cannot go anywhere, so the body does not move
+            (body.position, body.angle) = (previous.1, previous.2);
+            body.velocity = (0.0, 0.0);
+            body.angular_velocity = 0.0;
+            self.draw_body(body, previous.0, false);
+        }
+        true
+    }
+    /// Check if the given body can be placed at the given pose.
+    /// Bodies can only displace liquids and gases.
+    fn fits(&self, body: &RigidBody, position: (f32, f32), angle: f32) -> bool {
+        self.blocked_cells(body, position, angle).is_empty()
+    }
+    /// All board cells that block the given body at the given pose
+    fn blocked_cells(&self, body: &RigidBody, position: (f32, f32), angle: f32) -> Vec<(i32, i32)> {
+        body.rasterize(position, angle)
+            .into_iter()
+            .filter(|&(bx, by, _, _)| {
+                bx < 0
+                    || by < 0
+                    || bx >= self.viewport_width() as i32
+                    || by >= self.viewport_height() as i32
+                    || !matches!(
+                        self.board[bx as usize][by as usize].kind(),
+                        ElementKind::None | ElementKind::Liquid { .. } | ElementKind::Gas { .. }
+                    )
+            })
+            .map(|(bx, by, _, _)| (bx, by))
+            .collect()
+    }
+    /// Calculate the buoyant force, buoyant torque and submerged fraction of the given body.
+    /// A cell of the body counts as submerged if the row it is in borders a liquid.
+    fn buoyancy_of(&self, body: &RigidBody) -> (f32, f32, f32) {
+        let own: HashSet<(usize, usize)> =
+            body.occupied.iter().map(|&(x, y, _, _)| (x, y)).collect();
+        let liquid_density_at = |x: i32, y: usize| -> f32 {
+            if x < 0 || x >= self.viewport_width() as i32 {
+                return 0.0;
+            }
+            match self.board[x as usize][y].kind() {
+                ElementKind::Liquid { density } => density,
+                _ => 0.0,
+            }
+        };
+        let (mut buoyancy, mut torque, mut submerged) = (0.0, 0.0, 0usize);
+        for &(bx, by, _, _) in &body.occupied {
+            let mut left = bx as i32 - 1;
+            while left >= 0 && own.contains(&(left as usize, by)) {
+                left -= 1;
+            }
+            let mut right = bx as i32 + 1;
+            while own.contains(&(right as usize, by)) {
+                right += 1;
+            }
+            let fluid = liquid_density_at(left, by).max(liquid_density_at(right, by));
+            if fluid > 0.0 {
+                submerged += 1;
+                buoyancy += fluid;
+                // Upwards forces right of the center of mass rotate the body counterclockwise
+                torque -= fluid * (bx as f32 + 0.5 - body.position.0);
+            }
+        }
+        (
+            buoyancy,
+            torque,
+            submerged as f32 / body.occupied.len() as f32,
+        )
+    }
+    /// Draw the given body onto the board at its current pose, pushing all liquids and gases in its
+    /// way to the nearest free cells. If debris is set, the body is drawn as its debris instead.
+    /// Returns false without changing the board if the liquids and gases cannot go anywhere.
+    fn stamp(&mut self, body: &mut RigidBody, debris: bool) -> bool {
+        let cells: Vec<(usize, usize, usize, usize)> = body
+            .rasterize(body.position, body.angle)
+            .into_iter()
+            .map(|(bx, by, lx, ly)| (bx as usize, by as usize, lx, ly))
+            .collect();
+        let targets: HashSet<(usize, usize)> = cells.iter().map(|&(x, y, _, _)| (x, y)).collect();
+        let mut reserved = HashSet::new();
+        let mut displaced = Vec::new();
+        for &(x, y, _, _) in &cells {
+            if self.board[x][y] == Element::None {
+                continue;
+            }
+            match self.free_cell_near(x, y, &targets, &reserved) {
+                Some(free) => {
+                    reserved.insert(free);
+                    displaced.push(((x, y), free));
+                },
+                None => return false,
+            }
+        }
+        for ((x, y), (free_x, free_y)) in displaced {
+            self.replace_element(free_x, free_y, self.board[x][y]);
+        }
+        self.draw_body(body, cells, debris);
+        true
+    }
+    /// Write the cells of the given body to the given board cells,
+    /// given as (board x, board y, local x, local y)
+    fn draw_body(
+        &mut self,
+        body: &mut RigidBody,
+        cells: Vec<(usize, usize, usize, usize)>,
+        debris: bool,
+    ) {
+        body.occupied.clear();
+        for (bx, by, lx, ly) in cells {
+            if let Some(e) = body.cells[lx][ly] {
+                let e = match debris {
+                    true => e.debris().unwrap_or(e),
+                    false => e,
+                };
+                self.replace_element(bx, by, e);
+                body.occupied.push((bx, by, lx, ly));
+            }
+        }
+    }
+    /// Find the nearest empty cell outside of the given body cells that the liquid or gas at the
+    /// given position can flow to, without passing through solids or powders
+    fn free_cell_near(
+        &self,
+        x: usize,
+        y: usize,
+        body: &HashSet<(usize, usize)>,
+        reserved: &HashSet<(usize, usize)>,
+    ) -> Option<(usize, usize)> {
+        let mut visited = HashSet::from([(x, y)]);
+        let mut queue = VecDeque::from([(x, y)]);
+        while let Some((x, y)) = queue.pop_front() {
+            for &(x_offs, y_offs) in &PROBES {
+                let Some((n_x, n_y)) = self.offset(x, y, x_offs, y_offs) else {
+                    continue;
+                };
+                if !visited.insert((n_x, n_y)) {
+                    continue;
+                }
+                let element = self.board[n_x][n_y];
+                if body.contains(&(n_x, n_y)) {
+                    queue.push_back((n_x, n_y));
+                } else if element == Element::None && !reserved.contains(&(n_x, n_y)) {
+                    return Some((n_x, n_y));
+                } else if matches!(
+                    element.kind(),
+                    ElementKind::None | ElementKind::Liquid { .. } | ElementKind::Gas { .. }
+                ) {
+                    queue.push_back((n_x, n_y));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::world::GameWorld;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::collections::HashSet;
+
+    fn plank_in(liquid: Element) -> GameWorld {
+        let mut board = GameWorld::new(12, 20);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for x in 0..12 {
+            for y in 10..20 {
+                board.board[x][y] = liquid;
+            }
+        }
+        for x in 4..8 {
+            board.board[x][15] = Element::Wood;
+        }
+        board.create_rigid_bodies(0, 0, 11, 19);
+        for _ in 0..150 {
+            board.tick(&mut rng);
+        }
+        board
+    }
+    fn count(board: &GameWorld, element: Element) -> usize {
+        board
+            .board
+            .iter()
+            .flatten()
+            .filter(|&&e| e == element)
+            .count()
+    }
+    fn plank_height(board: &GameWorld) -> usize {
+        (0..20)
+            .find(|y| (0..12).any(|x| board.board[x][*y] == Element::Wood))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_wood_floats_on_water() {
+        let board = plank_in(Element::Water);
+        assert_eq!(board.rigid_bodies().len(), 1);
+        assert!(plank_height(&board) <= 11);
+    }
+    #[test]
+    fn test_wood_sinks_in_gasoline() {
+        let board = plank_in(Element::Gasoline);
+        assert_eq!(board.rigid_bodies().len(), 1);
+        assert!(plank_height(&board) >= 18);
+    }
+    #[test]
+    fn test_rotated_bodies_cover_each_cell_once() {
+        let mut board = GameWorld::new(20, 20);
+        for x in 4..12 {
+            for y in 8..10 {
+                board.board[x][y] = Element::Wood;
+            }
+        }
+        board.create_rigid_bodies(0, 0, 19, 19);
+        let body = &board.rigid_bodies()[0];
+        for step in 0..32 {
+            let cells = body.rasterize(body.position, step as f32 * 0.2);
+            let board_cells: HashSet<(i32, i32)> = cells.iter().map(|c| (c.0, c.1)).collect();
+            let local_cells: HashSet<(usize, usize)> = cells.iter().map(|c| (c.2, c.3)).collect();
+            assert_eq!(cells.len(), 16);
+            assert_eq!(board_cells.len(), 16);
+            assert_eq!(local_cells.len(), 16);
+        }
+    }
+    #[test]
+    fn test_off_center_impacts_rotate_bodies() {
+        let mut board = GameWorld::new(20, 20);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for y in 12..20 {
+            board.board[4][y] = Element::BrickWall;
+        }
+        for x in 3..13 {
+            board.board[x][5] = Element::Wood;
+        }
+        board.create_rigid_bodies(0, 0, 19, 10);
+        let mut max_angle: f32 = 0.0;
+        for _ in 0..60 {
+            board.tick(&mut rng);
+            max_angle = max_angle.max(board.rigid_bodies()[0].angle.abs());
+        }
+        assert!(max_angle > 0.2, "{}", max_angle);
+        assert_eq!(count(&board, Element::Wood), 10);
+    }
+    #[test]
+    fn test_bodies_collide_with_each_other() {
+        let mut board = GameWorld::new(20, 20);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for x in 0..20 {
+            board.board[x][19] = Element::BrickWall;
+        }
+        for x in 6..14 {
+            board.board[x][16] = Element::Wood;
+            board.board[x][17] = Element::Wood;
+        }
+        for x in 8..12 {
+            board.board[x][4] = Element::Stone;
+            board.board[x][5] = Element::Stone;
+        }
+        board.create_rigid_bodies(0, 0, 19, 18);
+        assert_eq!(board.rigid_bodies().len(), 2);
+        for _ in 0..100 {
+            board.tick(&mut rng);
+        }
+        assert_eq!(board.rigid_bodies().len(), 2);
+        assert_eq!(count(&board, Element::Wood), 16);
+        assert_eq!(count(&board, Element::Stone), 8);
+        // The stone block rests on top of the wooden plank
+        let top = |element| {
+            (0..20)
+                .find(|&y| (0..20).any(|x| board.board[x][y] == element))
+                .unwrap()
+        };
+        assert_eq!(top(Element::Wood), 17);
+        assert_eq!(top(Element::Stone), 15);
+    }
+    #[test]
+    fn test_hard_impacts_break_bodies() {
+        let mut board = GameWorld::new(20, 20);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for x in 0..20 {
+            board.board[x][19] = Element::BrickWall;
+        }
+        for x in 6..14 {
+            board.board[x][2] = Element::Stone;
+        }
+        board.create_rigid_bodies(0, 0, 19, 18);
+        board.rigid_bodies[0].angle = 0.3;
+        board.rigid_bodies[0].velocity = (0.0, 6.0);
+        for _ in 0..5 {
+            board.tick(&mut rng);
+        }
+        assert!(board.rigid_bodies().is_empty());
+        assert_eq!(count(&board, Element::Stone), 0);
+        assert_eq!(count(&board, Element::Sand), 8);
+    }
+    #[test]
+    fn test_bodies_keep_liquids_in_closed_tanks() {
+        let mut board = GameWorld::new(12, 12);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        for i in 0..12 {
+            for (x, y) in [(i, 0), (i, 11), (0, i), (11, i)] {
+                board.board[x][y] = Element::BrickWall;
+            }
+        }
+        for x in 1..11 {
+            for y in 1..11 {
+                board.board[x][y] = Element::Water;
+            }
+        }
+        for x in 3..8 {
+            board.board[x][8] = Element::Wood;
+        }
+        board.create_rigid_bodies(0, 0, 11, 11);
+        for _ in 0..50 {
+            board.tick(&mut rng);
+        }
+        assert_eq!(count(&board, Element::Water), 95);
+        assert_eq!(count(&board, Element::Wood), 5);
+    }
+}
@@ -0,0 +1,348 @@
+//   sandbox - World - selections and snippets
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::codec::{decode_element, encode_element};
+use crate::element::Element;
+use crate::shapes::polygon_fill;
+use crate::world::GameWorld;
+use embedded_graphics::Pixel;
+use embedded_graphics::prelude::{DrawTarget, Point, PointsIter};
+use embedded_graphics::primitives::{Line, Rectangle};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The first line of every serialized snippet
+const SNIPPET_HEADER: &str = "SANDBOX SNIPPET 2";
+/// The placeholder for transparent cells in serialized snippets
+const TRANSPARENT_CELL: &str = "-";
+
+/// A selected area of the board
+#[derive(Clone, Debug, PartialEq)]
+pub enum Selection {
+    /// A rectangle spanned by two corners, including both corners
+    Rectangle { from: (i32, i32), to: (i32, i32) },
+    /// The area enclosed by a freehand path, including the path itself
+    Lasso { path: Vec<(i32, i32)> },
+}
+
+impl Selection {
+    /// All selected cells. Cells may lie outside the world.
+    pub fn cells(&self) -> Vec<Point> {
+        let point = |&(x, y): &(i32, i32)| Point::new(x, y);
+        match self {
+            Selection::Rectangle { from, to } => Rectangle::with_corners(point(from), point(to))
+                .points()
+                .collect(),
+            Selection::Lasso { path } => {
+                let vertices: Vec<Point> = path.iter().map(point).collect();
+                let mut cells: Vec<Point> = polygon_fill(&vertices).collect();
+                for (&a, &b) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+                    cells.extend(Line::new(a, b).points());
+                }
+                cells.sort_by_key(|p| (p.x, p.y));
+                cells.dedup();
+                cells
+            },
+        }
+    }
+}
+
+/// A rectangular snippet of board cells that can be pasted into the world.
+/// Transparent cells, e.g., outside of a lasso selection, leave the world unchanged when pasted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snippet {
+    width: usize,
+    height: usize,
+    /// All cells, row by row
+    cells: Vec<Option<Element>>,
+}
+
+impl Snippet {
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+    /// Mirror this snippet at its vertical axis
+    pub fn flip_horizontal(&mut self) {
+        for row in self.cells.chunks_mut(self.width) {
+            row.reverse();
+        }
+    }
+    /// Mirror this snippet at its horizontal axis
+    pub fn flip_vertical(&mut self) {
+        self.cells = self
+            .cells
+            .chunks(self.width)
+            .rev()
+            .flatten()
+            .copied()
+            .collect();
+    }
+    /// Rotate this snippet by 90 degrees clockwise
+    pub fn rotate(&mut self) {
+        let (width, height) = (self.height, self.width);
+        self.cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.cells[(self.height - 1 - x) * self.width + y])
+            .collect();
+        (self.width, self.height) = (width, height);
+    }
+    /// All non-transparent cells of this snippet when it is pasted centered around the given cell
+    pub fn pixels_at(&self, center: Point) -> impl Iterator<Item = Pixel<Element>> + '_ {
+        let top_left = center - Point::new(self.width as i32 / 2, self.height as i32 / 2);
+        self.cells.iter().enumerate().filter_map(move |(i, cell)| {
+            cell.map(|element| {
+                let offset = Point::new((i % self.width) as i32, (i / self.width) as i32);
+                Pixel(top_left + offset, element)
+            })
+        })
+    }
+}
+
+/// Serializes the snippet as text, with one line per row and the encoded elements separated by commas
+impl Display for Snippet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", SNIPPET_HEADER)?;
+        writeln!(f, "{} {}", self.width, self.height)?;
+        for row in self.cells.chunks(self.width) {
+            let names: Vec<String> = row
+                .iter()
+                .map(|cell| match cell {
+                    Some(element) => encode_element(element),
+                    None => TRANSPARENT_CELL.to_string(),
+                })
+                .collect();
+            writeln!(f, "{}", names.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Snippet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        if lines.next().map(str::trim) != Some(SNIPPET_HEADER) {
+            return Err("Not a sandbox snippet".to_string());
+        }
+        let size: Vec<usize> = lines
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|t| t.parse().map_err(|_| "Invalid snippet size".to_string()))
+            .collect::<Result<_, _>>()?;
+        let [width, height] = size[..] else {
+            return Err("Invalid snippet size".to_string());
+        };
+        if width == 0 || height == 0 {
+            return Err("Empty snippet".to_string());
+        }
+        let cells: Vec<Option<Element>> = lines
+            .take(height)
+            .flat_map(|line| line.split(','))
+            .map(|name| match name.trim() {
+                TRANSPARENT_CELL => Ok(None),
+                encoded => decode_element(encoded).map(Some),
+            })
+            .collect::<Result<_, _>>()?;
+        if cells.len() != width * height {
+            return Err(format!(
+                "Expected {} cells, got {}",
+                width * height,
+                cells.len()
+            ));
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+}
+
+impl GameWorld {
+    /// Copy all selected cells into a snippet, or return None if no selected cell is inside the world
+    pub fn copy_selection(&self, selection: &Selection) -> Option<Snippet> {
+        let cells: Vec<Point> = selection
+            .cells()
+            .into_iter()
+            .filter(|p| {
+                p.x >= 0
+                    && p.y >= 0
+                    && p.x < self.viewport_width() as i32
+                    && p.y < self.viewport_height() as i32
+            })
+            .collect();
+        let min_x = cells.iter().map(|p| p.x).min()?;
+        let min_y = cells.iter().map(|p| p.y).min()?;
+        let width = (cells.iter().map(|p| p.x).max()? - min_x + 1) as usize;
+        let height = (cells.iter().map(|p| p.y).max()? - min_y + 1) as usize;
+        let mut snippet = Snippet {
+            width,
+            height,
+            cells: vec![None; width * height],
+        };
+        for p in cells {
+            snippet.cells[(p.y - min_y) as usize * width + (p.x - min_x) as usize] =
+                Some(self.board[p.x as usize][p.y as usize]);
+        }
+        Some(snippet)
+    }
+    /// Remove all elements inside the given selection
+    pub fn clear_selection(&mut self, selection: &Selection) {
+        self.draw_iter(
+            selection
+                .cells()
+                .into_iter()
+                .map(|p| Pixel(p, Element::None)),
+        )
+        .unwrap();
+    }
+    /// Paste the given snippet centered around the given cell
+    pub fn paste(&mut self, snippet: &Snippet, x: i32, y: i32) {
+        self.draw_iter(snippet.pixels_at(Point::new(x, y))).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::{
+        Agent, AgentState, CombustionProduct, CombustionProducts, Concentration, Element,
+        ElementKind, Heading,
+    };
+    use crate::world::GameWorld;
+    use crate::world::snippet::{Selection, Snippet};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    /// A world with stone, water and sand in a 2x2 square at (1, 1)
+    fn sample_board() -> GameWorld {
+        let mut board = GameWorld::new(10, 10);
+        board.board[1][1] = Element::Stone;
+        board.board[2][1] = Element::Water;
+        board.board[1][2] = Element::Sand;
+        board
+    }
+    fn sample_snippet() -> Snippet {
+        sample_board()
+            .copy_selection(&Selection::Rectangle {
+                from: (2, 2),
+                to: (1, 1),
+            })
+            .unwrap()
+    }
+    /// The lasso selection of the upper left triangle
+    fn triangle() -> Selection {
+        Selection::Lasso {
+            path: vec![(0, 0), (4, 0), (0, 4)],
+        }
+    }
+
+    #[test]
+    fn test_copy_rectangle_in_any_direction() {
+        let snippet = sample_snippet();
+        assert_eq!((snippet.width(), snippet.height()), (2, 2));
+    }
+    #[test]
+    fn test_paste_rotated_snippet() {
+        let mut board = sample_board();
+        let mut snippet = sample_snippet();
+        snippet.rotate();
+        board.paste(&snippet, 6, 6);
+        assert_eq!(board.board[5][5], Element::Sand);
+        assert_eq!(board.board[6][5], Element::Stone);
+        assert_eq!(board.board[6][6], Element::Water);
+    }
+    #[test]
+    fn test_flipping_both_axes_rotates_by_180_degrees() {
+        let mut snippet = sample_snippet();
+        let mut flipped = snippet.clone();
+        flipped.flip_horizontal();
+        flipped.flip_vertical();
+        snippet.rotate();
+        snippet.rotate();
+        assert_eq!(flipped, snippet);
+    }
+    #[test]
+    fn test_snippet_text_round_trip() {
+        let snippet = sample_snippet();
+        assert_eq!(snippet.to_string().parse::<Snippet>().unwrap(), snippet);
+    }
+    #[test]
+    fn test_cells_outside_of_lasso_are_transparent() {
+        let mut board = sample_board();
+        let lasso = board.copy_selection(&triangle()).unwrap();
+        board.board[4][4] = Element::Wood;
+        board.paste(&lasso, 2, 2);
+        assert_eq!(board.board[4][4], Element::Wood);
+        assert_eq!(board.board[1][1], Element::Stone);
+    }
+    #[test]
+    fn test_clear_lasso_selection() {
+        let mut board = sample_board();
+        board.board[4][4] = Element::Wood;
+        board.clear_selection(&triangle());
+        assert_eq!(board.board[1][1], Element::None);
+        assert_eq!(board.board[2][1], Element::None);
+        assert_eq!(board.board[4][4], Element::Wood);
+    }
+    #[test]
+    fn test_unknown_element_names_are_rejected() {
+        // Display names and lossy encodings are rejected
+        for cells in ["Unobtainium", "INVALID", "Saltwater", "BurningParticle"] {
+            let text = format!("SANDBOX SNIPPET 2\n1 1\n{}\n", cells);
+            assert!(text.parse::<Snippet>().is_err());
+        }
+    }
+    #[test]
+    fn test_snippet_text_keeps_element_data() {
+        let mut board = GameWorld::new(3, 1);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[0][0] = Element::SaltWater {
+            concentration: Concentration(37),
+        };
+        board.board[1][0] = Element::BurningParticle {
+            burned_element_kind: ElementKind::Solid,
+            decay_prob: 80,
+            flame_spawn_prob: 0.25,
+            products: CombustionProducts {
+                residue: Some(CombustionProduct::Ash),
+                exhaust: Some(CombustionProduct::Smoke),
+            },
+        };
+        board.board[2][0] = Element::Fish {
+            agent: Agent {
+                state: AgentState::Stranded { ticks: 9 },
+                heading: Heading::Right,
+            },
+        };
+        let selection = Selection::Rectangle {
+            from: (0, 0),
+            to: (2, 0),
+        };
+        let snippet = board.copy_selection(&selection).unwrap();
+        let parsed: Snippet = snippet.to_string().parse().unwrap();
+        assert_eq!(parsed, snippet);
+        let mut pasted = GameWorld::new(3, 1);
+        pasted.paste(&parsed, 1, 0);
+        assert_eq!(pasted.board, board.board);
+        // The pasted burning particle keeps its decay probability and can be simulated
+        pasted.tick(&mut rng);
+    }
+}
@@ -0,0 +1,141 @@
+//   sandbox - World - solutions and diffusion of dissolved salt
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::element::Element;
+use crate::world::{GameWorld, PROBES};
+use rand::prelude::IndexedRandom;
+use rand::{Rng, RngCore};
+
+/// The probability of a water cell to exchange dissolved salt with a neighbor in one tick
+const SOLUTE_DIFFUSION_PROBABILITY: f64 = 0.2;
+
+impl GameWorld {
+    /// Let dissolved salt diffuse between adjacent water and salt water cells
+    pub(in crate::world) fn diffuse_solutes(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) {
+        let Some(salinity) = self.board[x][y].salinity() else {
+            return;
+        };
+        if !rng.random_bool(SOLUTE_DIFFUSION_PROBABILITY) {
+            return;
+        }
+        let (x_offs, y_offs) = *PROBES.choose(rng).unwrap();
+        let Some((n_x, n_y)) = self.offset(x, y, x_offs, y_offs) else {
+            return;
+        };
+        let Some(other) = self.board[n_x][n_y].salinity() else {
+            return;
+        };
+        if salinity == other {
+            return;
+        }
+        // Split the salt evenly, randomly assigning the remainder
+        let total = salinity as u16 + other as u16;
+        let half = (total / 2) as u8;
+        let remainder = (total % 2) as u8;
+        let (mine, theirs) = match rng.random_bool(0.5) {
+            true => (half + remainder, half),
+            false => (half, half + remainder),
+        };
+        self.board[x][y] = Element::with_salinity(mine);
+        self.board[n_x][n_y] = Element::with_salinity(theirs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::{Concentration, Element};
+    use crate::world::GameWorld;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_salt_diffuses_and_is_conserved() {
+        let mut board = GameWorld::new(4, 1);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[0][0] = Element::SaltWater {
+            concentration: Concentration(80),
+        };
+        for x in 1..4 {
+            board.board[x][0] = Element::Water;
+        }
+        for _ in 0..200 {
+            for x in 0..4 {
+                board.diffuse_solutes(x, 0, &mut rng);
+            }
+        }
+        let salinity: Vec<u8> = (0..4)
+            .map(|x| board.board[x][0].salinity().unwrap())
+            .collect();
+        assert_eq!(salinity.iter().map(|c| *c as u16).sum::<u16>(), 80);
+        assert!(salinity.iter().all(|c| (15..=25).contains(c)));
+    }
+    #[test]
+    fn test_evaporation_returns_dissolved_salt() {
+        let mut board = GameWorld::new(3, 3);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let grains = [(0, 1), (2, 1), (1, 0), (1, 2)];
+        board.board[1][1] = Element::Water;
+        for (x, y) in grains {
+            board.board[x][y] = Element::Salt;
+        }
+        while grains
+            .iter()
+            .any(|&(x, y)| board.board[x][y] == Element::Salt)
+        {
+            for (x, y) in grains {
+                board.transmute(x, y, &mut rng);
+            }
+        }
+        assert_eq!(board.board[1][1].salinity(), Some(80));
+        board.board[1][0] = Element::FireSource;
+        while board.board[1][1].salinity().is_some() {
+            board.transmute(1, 1, &mut rng);
+        }
+        let salt = board.board.iter().flatten();
+        assert_eq!(salt.filter(|e| **e == Element::Salt).count(), 4);
+    }
+    /// Let the given element at the top center of a 3x3 world transmute the salt water with a
+    /// concentration of two grains next to it, and count the salt grains left behind
+    fn salt_left_by(element: Element, water: (usize, usize)) -> usize {
+        let mut board = GameWorld::new(3, 3);
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        board.board[1][0] = element;
+        board.board[water.0][water.1] = Element::SaltWater {
+            concentration: Concentration(40),
+        };
+        while board.board[water.0][water.1].salinity().is_some() {
+            board.transmute(1, 0, &mut rng);
+        }
+        let salt = board.board.iter().flatten();
+        salt.filter(|e| **e == Element::Salt).count()
+    }
+    #[test]
+    fn test_lava_leaves_dissolved_salt_behind() {
+        assert_eq!(salt_left_by(Element::Lava, (1, 1)), 2);
+    }
+    #[test]
+    fn test_dust_leaves_dissolved_salt_behind() {
+        assert_eq!(salt_left_by(Element::Dust, (0, 0)), 2);
+    }
+    #[test]
+    fn test_freezing_leaves_dissolved_salt_behind() {
+        assert_eq!(salt_left_by(Element::Cryo, (1, 1)), 2);
+    }
+    #[test]
+    fn test_molten_glass_leaves_dissolved_salt_behind() {
+        assert_eq!(salt_left_by(Element::MoltenGlass, (1, 1)), 2);
+    }
+}
@@ -0,0 +1,120 @@
+//   sandbox - World - flood fill and replace tools
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::element::Element;
+use crate::world::{GameWorld, PROBES};
+use std::mem::discriminant;
+
+/// The offsets of the eight adjacent cells of a cell, including the diagonally adjacent cells
+const PROBES_8: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl GameWorld {
+    /// Fill the contiguous region of the element at the given cell with the given element.
+    /// If diagonal is set, cells that only touch at their corners are part of the region, too.
+    pub(crate) fn flood_fill(&mut self, x: usize, y: usize, element: Element, diagonal: bool) {
+        let region = discriminant(&self.board[x][y]);
+        if region == discriminant(&element) {
+            return;
+        }
+        let probes: &[(i32, i32)] = match diagonal {
+            true => &PROBES_8,
+            false => &PROBES,
+        };
+        let mut stack = vec![(x, y)];
+        self.set_element(x, y, element);
+        while let Some((x, y)) = stack.pop() {
+            for &(x_offs, y_offs) in probes {
+                if let Some((n_x, n_y)) = self.offset(x, y, x_offs, y_offs)
+                    && discriminant(&self.board[n_x][n_y]) == region
+                {
+                    self.set_element(n_x, n_y, element);
+                    stack.push((n_x, n_y));
+                }
+            }
+        }
+    }
+    /// Replace all cells of the source element inside the square of the given size around the given
+    /// cell with the given element, leaving all other elements intact
+    pub(crate) fn replace_at(
+        &mut self,
+        x: i32,
+        y: i32,
+        size: u32,
+        source: Element,
+        element: Element,
+    ) {
+        let half = size as i32 / 2;
+        for r_x in (x - half)..(x - half + size as i32) {
+            for r_y in (y - half)..(y - half + size as i32) {
+                if r_x >= 0
+                    && r_y >= 0
+                    && r_x < self.viewport_width() as i32
+                    && r_y < self.viewport_height() as i32
+                    && discriminant(&self.board[r_x as usize][r_y as usize])
+                        == discriminant(&source)
+                {
+                    self.set_element(r_x as usize, r_y as usize, element);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::world::GameWorld;
+
+    #[test]
+    fn test_flood_fill_stops_at_diagonal_wall() {
+        let mut board = GameWorld::new(5, 5);
+        for i in 0..5 {
+            board.board[i][4 - i] = Element::BrickWall;
+        }
+        board.flood_fill(0, 0, Element::Water, false);
+        assert_eq!(board.board[0][0], Element::Water);
+        assert_eq!(board.board[4][4], Element::None);
+    }
+    #[test]
+    fn test_diagonal_flood_fill_leaks_through_diagonal_wall() {
+        let mut board = GameWorld::new(5, 5);
+        for i in 0..5 {
+            board.board[i][4 - i] = Element::BrickWall;
+        }
+        board.flood_fill(0, 0, Element::Water, true);
+        assert_eq!(board.board[4][4], Element::Water);
+    }
+    #[test]
+    fn test_replace_leaves_other_elements_intact() {
+        let mut board = GameWorld::new(5, 5);
+        for x in 0..5 {
+            board.board[x][2] = Element::Water;
+            board.board[x][3] = Element::BrickWall;
+        }
+        board.replace_at(2, 2, 5, Element::Water, Element::Lava);
+        assert!((0..5).all(|x| board.board[x][2] == Element::Lava));
+        assert!((0..5).all(|x| board.board[x][3] == Element::BrickWall));
+    }
+}
@@ -14,9 +14,10 @@
 //   You should have received a copy of the GNU General Public License
 //   along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::element::{Element, ElementKind, Flammability};
-use crate::world::GameWorld;
+use crate::element::{Concentration, Element, ElementKind, Flammability, MAX_CONCENTRATION};
+use crate::world::{GameWorld, PROBES};
 use rand::prelude::IndexedRandom;
+use rand::seq::SliceRandom;
 use rand::{Rng, RngCore};
 
 enum Transmutation {
@@ -39,10 +40,66 @@ enum Transmutation {
         outcome_b: Vec<Element>,
         outcome_a: Option<Element>,
     },
+    /// The salt water among a and b loses its water, turning into W and leaving its dissolved salt
+    /// behind, and the other element transforms into one of O
+    Evaporation {
+        probability: f64,
+        outcome_water: Option<Element>,
+        outcome_other: Vec<Element>,
+    },
 }
 
 const LIQUID_SOURCE_SPAWN_PROBABILITY: f64 = 0.015;
 const GAS_SOURCE_SPAWN_PROBABILITY: f64 = 0.025;
+/// The probability of acid to dissolve an element without any corrosion resistance
+const ACID_CORROSION_PROBABILITY: f64 = 0.2;
+/// The increase of the salt concentration of water when dissolving one grain of salt
+const SALT_PER_GRAIN: u8 = 20;
+/// The temperature in °C below which adjacent elements cool molten glass down
+const GLASS_COOLING_TEMPERATURE: f32 = 500.0;
+/// The probability of a liquid to freeze when touching an element 40°C below its freezing point
+const FREEZE_PROBABILITY: f64 = 0.2;
+/// The probability of ice and snow to melt when touching an element of 1000°C
+const MELT_PROBABILITY: f64 = 0.2;
+/// The probability of snow to compact into ice in one tick while being under load
+const SNOW_COMPACTION_PROBABILITY: f64 = 0.002;
+/// The number of cells on top of snow that are needed to compact it into ice
+const SNOW_COMPACTION_LOAD: usize = 8;
+
+/// Given a cold element a and an adjacent liquid b, can a freeze b?
+fn can_freeze(a: &Element, b: &Element) -> Transmutation {
+    match (a.temperature(), b.freezing_point()) {
+        (Some(temperature), Some(freezing_point)) if temperature < freezing_point => {
+            let probability = ((freezing_point - temperature) as f64 / 40.0).min(1.0);
+            match b {
+                // Salt is pushed out of the freezing salt water
+                Element::SaltWater { .. } => Transmutation::Evaporation {
+                    probability: probability * FREEZE_PROBABILITY,
+                    outcome_water: Some(Element::Ice),
+                    outcome_other: vec![*a],
+                },
+                _ => Transmutation::WithProbability {
+                    probability: probability * FREEZE_PROBABILITY,
+                    outcome_a: Some(*a),
+                    outcome_b: Some(Element::Ice),
+                },
+            }
+        },
+        _ => Transmutation::None,
+    }
+}
+
+/// Given a frozen element a and an adjacent element b, can b melt a?
+fn can_melt(b: &Element) -> Transmutation {
+    match b.temperature() {
+        Some(temperature) if temperature > 0.0 => Transmutation::WithProbability {
+            probability: (temperature as f64 / 1000.0).min(1.0) * MELT_PROBABILITY,
+            outcome_a: Some(Element::Water),
+            outcome_b: Some(*b),
+        },
+        _ => Transmutation::None,
+    }
+}
 
 /// Given adjacent elements a and b, can a transmute b?
 fn can_transmute(a: &Element, b: &Element) -> Transmutation {
@@ -50,13 +107,35 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
         Element::None => Transmutation::None,
         Element::BrickWall => Transmutation::None,
         Element::Wood => Transmutation::None,
-        Element::Sand => Transmutation::None,
+        Element::Stone => Transmutation::None,
+        Element::Sand => match b {
+            // Sand melts into glass when touching extremely hot elements
+            Element::Lava | Element::Volcano => Transmutation::WithProbability {
+                probability: 0.02,
+                outcome_a: Some(Element::MoltenGlass),
+                outcome_b: Some(*b),
+            },
+            // Sand only melts in sustained fire
+            Element::Flame => Transmutation::WithProbability {
+                probability: 0.001,
+                outcome_a: Some(Element::MoltenGlass),
+                outcome_b: Some(*b),
+            },
+            _ => Transmutation::None,
+        },
         Element::Salt => match b {
-            // Salt and water transforms into saltwater:
-            Element::Water => Transmutation::WithProbability {
-                probability: 0.05,
-                outcome_a: None,
-                outcome_b: Some(Element::SaltWater),
+            // Salt dissolves in water and unsaturated salt water
+            e if e
+                .salinity()
+                .is_some_and(|c| c + SALT_PER_GRAIN <= MAX_CONCENTRATION) =>
+            {
+                Transmutation::WithProbability {
+                    probability: 0.05,
+                    outcome_a: None,
+                    outcome_b: Some(Element::with_salinity(
+                        e.salinity().unwrap() + SALT_PER_GRAIN,
+                    )),
+                }
             },
             _ => Transmutation::None,
         },
@@ -89,24 +168,24 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
             },
             _ => Transmutation::None,
         },
-        Element::SaltWater => match b {
+        Element::SaltWater { .. } => match b {
             // Salt Water extinguishes Flames
-            Element::Flame => Transmutation::WithProbability {
+            Element::Flame => Transmutation::Evaporation {
                 probability: 0.1,
-                outcome_a: Some(Element::Salt),
-                outcome_b: Some(Element::Steam),
+                outcome_water: Some(Element::Steam),
+                outcome_other: vec![Element::Steam],
             },
-            // Salt Water boils when touching hot surfaces, having a chance of turning into salt or steam
-            Element::FireSource => Transmutation::WithProbabilityOfMultipleA {
+            // Salt Water boils when touching hot surfaces, leaving its salt behind
+            Element::FireSource => Transmutation::Evaporation {
                 probability: 0.01,
-                outcome_a: vec![Element::Steam, Element::Salt],
-                outcome_b: Some(Element::FireSource),
+                outcome_water: Some(Element::Steam),
+                outcome_other: vec![Element::FireSource],
             },
             // Salt Water boils immediately when touching very hot surfaces
-            Element::Volcano => Transmutation::WithProbabilityOfMultipleA {
+            Element::Volcano => Transmutation::Evaporation {
                 probability: 0.1,
-                outcome_a: vec![Element::Steam, Element::Salt],
-                outcome_b: Some(Element::Volcano),
+                outcome_water: Some(Element::Steam),
+                outcome_other: vec![Element::Volcano],
             },
             _ => Transmutation::None,
         },
@@ -175,6 +254,7 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                     prob,
                     decay_prob,
                     flame_spawn_prob,
+                    products,
                 } => Transmutation::WithProbability {
                     probability: (prob * 1.5).min(1.0),
                     outcome_a: Some(*a),
@@ -182,7 +262,7 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                         burned_element_kind: b.kind(),
                         decay_prob,
                         flame_spawn_prob,
-                        spawns_ash: matches!(e, Element::Wood),
+                        products,
                     }),
                 },
             },
@@ -210,11 +290,11 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                 outcome_a: Some(Element::WetDust),
                 outcome_b: None,
             },
-            // Transforms to salt and wet dust in salt water
-            Element::SaltWater => Transmutation::WithProbability {
+            // Transforms to wet dust in salt water, soaking up the water and leaving the salt behind
+            Element::SaltWater { .. } => Transmutation::Evaporation {
                 probability: 0.005,
-                outcome_a: Some(Element::Salt),
-                outcome_b: Some(Element::WetDust),
+                outcome_water: None,
+                outcome_other: vec![Element::WetDust],
             },
             _ => Transmutation::None,
         },
@@ -251,6 +331,7 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                 prob,
                 decay_prob,
                 flame_spawn_prob,
+                products,
             } => Transmutation::WithProbability {
                 probability: prob,
                 outcome_a: None,
@@ -258,18 +339,27 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                     burned_element_kind: b.kind(),
                     decay_prob,
                     flame_spawn_prob,
-                    spawns_ash: matches!(b, Element::Wood),
+                    products,
                 }),
             },
         },
         Element::BurningParticle {
-            flame_spawn_prob, ..
+            flame_spawn_prob,
+            products,
+            ..
         } => match b {
-            // Burning Particles spawn flames
-            Element::None => Transmutation::WithProbability {
-                probability: *flame_spawn_prob,
-                outcome_a: Some(*a),
-                outcome_b: Some(Element::Flame),
+            // Burning Particles spawn flames and their exhaust gas
+            Element::None => match products.exhaust {
+                None => Transmutation::WithProbability {
+                    probability: *flame_spawn_prob,
+                    outcome_a: Some(*a),
+                    outcome_b: Some(Element::Flame),
+                },
+                Some(exhaust) => Transmutation::WithProbabilityOfMultipleB {
+                    probability: *flame_spawn_prob,
+                    outcome_a: Some(*a),
+                    outcome_b: vec![Element::Flame, Element::Flame, exhaust.element()],
+                },
             },
             // Burning particles can light other burning particles on fire, but with a lower probability than flames.
             e => match e.flammability() {
@@ -278,6 +368,7 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                     prob,
                     decay_prob,
                     flame_spawn_prob,
+                    products,
                 } => Transmutation::WithProbability {
                     probability: prob * 0.5,
                     outcome_a: Some(*a),
@@ -285,12 +376,13 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                         burned_element_kind: b.kind(),
                         decay_prob,
                         flame_spawn_prob,
-                        spawns_ash: matches!(e, Element::Wood),
+                        products,
                     }),
                 },
             },
         },
         Element::Gasoline => Transmutation::None,
+        Element::Honey => Transmutation::None,
         Element::Methane => Transmutation::None,
         Element::Ash => match b {
             // Ash has a small chance of despawning when touching a flame
@@ -306,7 +398,7 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                 outcome_b: Some(*b),
             },
             // Ash has a small chance of dissolving in water and salt water
-            Element::SaltWater => Transmutation::WithProbability {
+            Element::SaltWater { .. } => Transmutation::WithProbability {
                 probability: 0.001,
                 outcome_a: None,
                 outcome_b: Some(*b),
@@ -333,16 +425,11 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                 outcome_a: vec![Element::Lava, Element::ColdLava, Element::ColdLava],
                 outcome_b: Some(Element::Steam),
             },
-            // Lava evaporates Salt Water and might turn into salt in the process
-            Element::SaltWater => Transmutation::WithProbabilityOfMultipleA {
+            // Lava evaporates Salt Water, leaving its salt behind
+            Element::SaltWater { .. } => Transmutation::Evaporation {
                 probability: 0.5,
-                outcome_a: vec![
-                    Element::Lava,
-                    Element::ColdLava,
-                    Element::ColdLava,
-                    Element::Salt,
-                ],
-                outcome_b: Some(Element::Steam),
+                outcome_water: Some(Element::Steam),
+                outcome_other: vec![Element::Lava, Element::ColdLava, Element::ColdLava],
             },
             // Lava has a very small chance of emitting flames
             Element::None => Transmutation::WithProbability {
@@ -357,6 +444,7 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                     prob,
                     decay_prob,
                     flame_spawn_prob,
+                    products,
                 } => Transmutation::WithProbability {
                     probability: prob * 0.1,
                     outcome_a: Some(*a),
@@ -364,40 +452,144 @@ fn can_transmute(a: &Element, b: &Element) -> Transmutation {
                         burned_element_kind: b.kind(),
                         decay_prob,
                         flame_spawn_prob,
-                        spawns_ash: matches!(e, Element::Wood),
+                        products,
                     }),
                 },
             },
         },
+        Element::Acid => match b {
+            // Acid is neutralized by ash. Neutralization by lye is part of the reaction table.
+            Element::Ash => Transmutation::WithProbability {
+                probability: 0.2,
+                outcome_a: Some(Element::SaltWater {
+                    concentration: Concentration::default(),
+                }),
+                outcome_b: None,
+            },
+            // Acid dissolves all other elements depending on their corrosion resistance.
+            // It is slowly used up in the process, releasing toxic gas.
+            e if e.corrosion_resistance() < 1.0 => Transmutation::WithProbabilityOfMultipleA {
+                probability: ACID_CORROSION_PROBABILITY * (1.0 - e.corrosion_resistance()),
+                outcome_a: vec![Element::Acid, Element::Acid, Element::ToxicGas],
+                outcome_b: None,
+            },
+            _ => Transmutation::None,
+        },
+        Element::Lye => Transmutation::None,
+        Element::Glass => Transmutation::None,
+        Element::ToxicGas => Transmutation::None,
+        // Ice and snow melt near hot elements and slowly freeze adjacent water
+        Element::Ice | Element::Snow => match can_melt(b) {
+            Transmutation::None => can_freeze(a, b),
+            melt => melt,
+        },
+        // Cryo freezes water and salt water
+        Element::Cryo => can_freeze(a, b),
+        Element::MoltenGlass => match b {
+            // Molten glass evaporates water and solidifies immediately
+            Element::Water => Transmutation::WithProbability {
+                probability: 0.5,
+                outcome_a: Some(Element::Glass),
+                outcome_b: Some(Element::Steam),
+            },
+            Element::SaltWater { .. } => Transmutation::Evaporation {
+                probability: 0.5,
+                outcome_water: Some(Element::Steam),
+                outcome_other: vec![Element::Glass],
+            },
+            // Molten glass slowly cools down into glass when touching colder elements
+            e if e.temperature().unwrap_or(0.0) < GLASS_COOLING_TEMPERATURE => {
+                Transmutation::WithProbability {
+                    probability: 0.005,
+                    outcome_a: Some(Element::Glass),
+                    outcome_b: Some(*b),
+                }
+            },
+            _ => Transmutation::None,
+        },
+        Element::Seed => Transmutation::None,
+        Element::Plant { .. } => Transmutation::None,
+        Element::Vine => Transmutation::None,
+        Element::Fungus => Transmutation::None,
+        Element::Ant { .. } => Transmutation::None,
+        Element::Fish { .. } => Transmutation::None,
+        Element::Bird { .. } => Transmutation::None,
+        Element::Soot => Transmutation::None,
+        Element::Smoke => Transmutation::None,
+        Element::BlackSmoke => Transmutation::None,
+        Element::Oxygen => Transmutation::None,
     }
 }
 
 impl GameWorld {
     /// Decay all decaying elements in the world
     pub(in crate::world) fn decay(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) {
+        // Flames without oxygen are extinguished
+        if self.board[x][y] == Element::Flame && !self.can_burn(x, y) {
+            self.replace_element(x, y, Element::None);
+            return;
+        }
         if let Some(decay_prob) = self.board[x][y].decay_prob() {
             if rng.random_bool(decay_prob) {
                 // Decay the element
                 match self.board[x][y].decays_to() {
-                    None => self.board[x][y] = Element::None,
-                    Some(e) => self.board[x][y] = e,
+                    None => self.replace_element(x, y, Element::None),
+                    Some(e) => self.replace_element(x, y, e),
                 }
             }
         }
     }
+    /// The number of non-gaseous elements stacked directly on top of the given cell
+    fn load_above(&self, x: usize, y: usize) -> usize {
+        (0..y)
+            .rev()
+            .take_while(|&y| {
+                !matches!(
+                    self.board[x][y].kind(),
+                    ElementKind::None | ElementKind::Gas { .. }
+                )
+            })
+            .count()
+    }
     /// Randomly transmute elements in the world
     pub(in crate::world) fn transmute(&mut self, x: usize, y: usize, rng: &mut dyn RngCore) {
         let height = self.viewport_height();
         let width = self.viewport_width();
+        // Snow compacts into ice under load
+        if self.board[x][y] == Element::Snow
+            && self.load_above(x, y) >= SNOW_COMPACTION_LOAD
+            && rng.random_bool(SNOW_COMPACTION_PROBABILITY)
+        {
+            self.replace_element(x, y, Element::Ice);
+            return;
+        }
         // Make it most probable to transform the top, then left right, then bottom elements.
         // Important for fire!
         let probes = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+        // Fires consume oxygen. Burning particles without oxygen smolder without spreading.
+        if matches!(
+            self.board[x][y],
+            Element::Flame | Element::BurningParticle { .. }
+        ) {
+            if !self.can_burn(x, y) {
+                return;
+            }
+            self.consume_oxygen(x, y);
+        }
         for (x_offs, y_offs) in probes {
             let b_x = x as i32 + x_offs;
             let b_y = y as i32 + y_offs;
             if b_x < 0 || b_x >= width as i32 || b_y < 0 || b_y >= height as i32 {
                 continue;
             }
+            // Dust floating on the surface of a liquid stays dry, only submerged dust soaks
+            if self.board[x][y] == Element::Dust
+                && y_offs == 1
+                && (y == 0
+                    || !self.board[x][y - 1].is_kind_of(&ElementKind::Liquid { density: 0.0 }))
+            {
+                continue;
+            }
             match can_transmute(&self.board[x][y], &self.board[b_x as usize][b_y as usize]) {
                 Transmutation::None => continue,
                 Transmutation::WithProbability {
@@ -407,8 +599,12 @@ impl GameWorld {
                 } => {
                     if rng.random_bool(probability) {
                         // Transmute!
-                        self.board[x][y] = outcome_a.unwrap_or(Element::None);
-                        self.board[b_x as usize][b_y as usize] = outcome_b.unwrap_or(Element::None);
+                        self.replace_element(x, y, outcome_a.unwrap_or(Element::None));
+                        self.replace_element(
+                            b_x as usize,
+                            b_y as usize,
+                            outcome_b.unwrap_or(Element::None),
+                        );
                     }
                 },
                 Transmutation::WithProbabilityOfMultipleA {
@@ -417,8 +613,16 @@ impl GameWorld {
                     outcome_b,
                 } => {
                     if rng.random_bool(probability) {
-                        self.board[x][y] = *outcome_a.choose(rng).unwrap_or(&Element::None);
-                        self.board[b_x as usize][b_y as usize] = outcome_b.unwrap_or(Element::None);
+                        self.replace_element(
+                            x,
+                            y,
+                            *outcome_a.choose(rng).unwrap_or(&Element::None),
+                        );
+                        self.replace_element(
+                            b_x as usize,
+                            b_y as usize,
+                            outcome_b.unwrap_or(Element::None),
+                        );
                     }
                 },
                 Transmutation::WithProbabilityOfMultipleB {
@@ -427,12 +631,81 @@ impl GameWorld {
                     outcome_b,
                 } => {
                     if rng.random_bool(probability) {
-                        self.board[x][y] = outcome_a.unwrap_or(Element::None);
-                        self.board[b_x as usize][b_y as usize] =
-                            *outcome_b.choose(rng).unwrap_or(&Element::None);
+                        self.replace_element(x, y, outcome_a.unwrap_or(Element::None));
+                        self.replace_element(
+                            b_x as usize,
+                            b_y as usize,
+                            *outcome_b.choose(rng).unwrap_or(&Element::None),
+                        );
+                    }
+                },
+                Transmutation::Evaporation {
+                    probability,
+                    outcome_water,
+                    outcome_other,
+                } => {
+                    if rng.random_bool(probability) {
+                        let (water, other) = match self.board[x][y] {
+                            Element::SaltWater { .. } => ((x, y), (b_x as usize, b_y as usize)),
+                            _ => ((b_x as usize, b_y as usize), (x, y)),
+                        };
+                        self.replace_element(
+                            other.0,
+                            other.1,
+                            *outcome_other.choose(rng).unwrap_or(&Element::None),
+                        );
+                        self.evaporate(water.0, water.1, outcome_water, rng);
                     }
                 },
             }
         }
     }
+    /// Let the salt water at the given cell lose its water, turning into the given element
+    /// (e.g., steam), and leave its dissolved salt behind.
+    /// The salt crystallizes into grains in the cell itself and in the adjacent empty cells.
+    /// Salt that is not enough for a whole grain stays dissolved in the adjacent water.
+    fn evaporate(&mut self, x: usize, y: usize, outcome: Option<Element>, rng: &mut dyn RngCore) {
+        let mut salt = self.board[x][y].salinity().unwrap_or(0);
+        let outcome = outcome.unwrap_or(Element::None);
+        self.replace_element(x, y, outcome);
+        let mut empty: Vec<(usize, usize)> = PROBES
+            .iter()
+            .filter_map(|&(x_offs, y_offs)| self.offset(x, y, x_offs, y_offs))
+            .filter(|&(e_x, e_y)| self.board[e_x][e_y] == Element::None)
+            .collect();
+        empty.shuffle(rng);
+        for (g_x, g_y) in std::iter::once((x, y)).chain(empty) {
+            if salt < SALT_PER_GRAIN {
+                break;
+            }
+            self.replace_element(g_x, g_y, Element::Salt);
+            salt -= SALT_PER_GRAIN;
+        }
+        if salt == 0 {
+            return;
+        }
+        let solvent = PROBES
+            .iter()
+            .filter_map(|&(x_offs, y_offs)| self.offset(x, y, x_offs, y_offs))
+            .find(|&(s_x, s_y)| {
+                self.board[s_x][s_y]
+                    .salinity()
+                    .is_some_and(|c| c as u16 + salt as u16 <= MAX_CONCENTRATION as u16)
+            });
+        match solvent {
+            Some((s_x, s_y)) => {
+                let salinity = self.board[s_x][s_y].salinity().unwrap() + salt;
+                self.replace_element(s_x, s_y, Element::with_salinity(salinity));
+            },
+            // Without any water around, the rest of the salt crystallizes with a probability
+            // proportional to its amount
+            None => {
+                if self.board[x][y] == outcome
+                    && rng.random_bool((salt as f64 / SALT_PER_GRAIN as f64).min(1.0))
+                {
+                    self.replace_element(x, y, Element::Salt);
+                }
+            },
+        }
+    }
 }
@@ -0,0 +1,348 @@
+//   sandbox - World - saving and loading the complete world state
+//   Copyright (C) 2025 Lukas Kirschner
+//
+//   This program is free software: you can redistribute it and/or modify
+//   it under the terms of the GNU General Public License as published by
+//   the Free Software Foundation, either version 3 of the License, or
+//   (at your option) any later version.
+//
+//   This program is distributed in the hope that it will be useful,
+//   but WITHOUT ANY WARRANTY; without even the implied warranty of
+//   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//   GNU General Public License for more details.
+//
+//   You should have received a copy of the GNU General Public License
+//   along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::codec::{decode_element, encode_element};
+use crate::world::gravity::{AttractorKind, GravityField};
+use crate::world::oxygen::MAX_OXYGEN;
+use crate::world::rigid_body::RigidBody;
+use crate::world::{GameWorld, MAX_WORLD_SIZE, MIN_WORLD_SIZE};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The first line of every saved world
+const WORLD_HEADER: &str = "SANDBOX WORLD 1";
+/// The prefix of lines containing a gravity field
+const FIELD_PREFIX: &str = "field ";
+/// The prefix of lines containing a rigid body
+const BODY_PREFIX: &str = "body ";
+
+const fn attractor_kind_name(kind: &AttractorKind) -> &'static str {
+    match kind {
+        AttractorKind::Attractor => "Attractor",
+        AttractorKind::Repulsor => "Repulsor",
+        AttractorKind::BlackHole => "BlackHole",
+        AttractorKind::WhiteHole => "WhiteHole",
+    }
+}
+
+fn encode_field(field: &GravityField) -> String {
+    match field {
+        GravityField::Zone {
+            x,
+            y,
+            width,
+            height,
+            gravity,
+        } => format!(
+            "Zone {} {} {} {} {} {}",
+            x, y, width, height, gravity.0, gravity.1
+        ),
+        GravityField::Attractor {
+            x,
+            y,
+            radius,
+            strength,
+            kind,
+            emits,
+        } => format!(
+            "Attractor {} {} {} {} {} {}",
+            x,
+            y,
+            radius,
+            strength,
+            attractor_kind_name(kind),
+            encode_element(emits)
+        ),
+    }
+}
+
+/// Parse a whitespace-separated field of a saved world
+pub(in crate::world) fn parse_field<T: FromStr>(token: Option<&str>) -> Result<T, String> {
+    let token = token.ok_or_else(|| "Missing value".to_string())?;
+    token
+        .parse()
+        .map_err(|_| format!("Invalid value '{}'", token))
+}
+
+/// Decode a gravity field, which must lie completely inside a world of the given size
+fn decode_field(encoded: &str, width: usize, height: usize) -> Result<GravityField, String> {
+    let mut tokens = encoded.split_whitespace();
+    let field = match tokens.next() {
+        Some("Zone") => {
+            let (x, y): (usize, usize) = (parse_field(tokens.next())?, parse_field(tokens.next())?);
+            let size: (usize, usize) = (parse_field(tokens.next())?, parse_field(tokens.next())?);
+            let gravity: (f32, f32) = (parse_field(tokens.next())?, parse_field(tokens.next())?);
+            if x + size.0 > width || y + size.1 > height {
+                return Err("Gravity zone outside of the world".to_string());
+            }
+            if !gravity.0.is_finite() || !gravity.1.is_finite() {
+                return Err("Invalid gravity".to_string());
+            }
+            GravityField::Zone {
+                x,
+                y,
+                width: size.0,
+                height: size.1,
+                gravity,
+            }
+        },
+        Some("Attractor") => {
+            let (x, y): (usize, usize) = (parse_field(tokens.next())?, parse_field(tokens.next())?);
+            let radius: usize = parse_field(tokens.next())?;
+            let strength: f32 = parse_field(tokens.next())?;
+            let kind_name = tokens.next().unwrap_or_default();
+            let kind = [
+                AttractorKind::Attractor,
+                AttractorKind::Repulsor,
+                AttractorKind::BlackHole,
+                AttractorKind::WhiteHole,
+            ]
+            .into_iter()
+            .find(|k| attractor_kind_name(k) == kind_name)
+            .ok_or_else(|| format!("Unknown attractor kind '{}'", kind_name))?;
+            let emits = decode_element(tokens.next().unwrap_or_default())?;
+            if x >= width || y >= height || radius == 0 || !strength.is_finite() {
+                return Err("Invalid attractor".to_string());
+            }
+            GravityField::Attractor {
+                x,
+                y,
+                radius,
+                strength,
+                kind,
+                emits,
+            }
+        },
+        _ => return Err(format!("Unknown gravity field '{}'", encoded)),
+    };
+    if tokens.next().is_some() {
+        return Err(format!("Invalid gravity field '{}'", encoded));
+    }
+    Ok(field)
+}
+
+/// Serializes the complete state of the simulated world as text:
+/// the size and liquid drag, all cells with their element data row by row, the oxygen content
+/// of all cells row by row, and one line per gravity field and rigid body.
+impl Display for GameWorld {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (width, height) = (self.viewport_width(), self.viewport_height());
+        writeln!(f, "{}", WORLD_HEADER)?;
+        writeln!(f, "{} {} {}", width, height, self.liquid_drag)?;
+        for y in 0..height {
+            let row: Vec<String> = (0..width)
+                .map(|x| encode_element(&self.board[x][y]))
+                .collect();
+            writeln!(f, "{}", row.join(","))?;
+        }
+        for y in 0..height {
+            let row: Vec<String> = (0..width).map(|x| self.oxygen[x][y].to_string()).collect();
+            writeln!(f, "{}", row.join(","))?;
+        }
+        for field in &self.gravity_fields {
+            writeln!(f, "{}{}", FIELD_PREFIX, encode_field(field))?;
+        }
+        for body in &self.rigid_bodies {
+            writeln!(f, "{}{}", BODY_PREFIX, body.encode())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameWorld {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        if lines.next().map(str::trim) != Some(WORLD_HEADER) {
+            return Err("Not a sandbox world".to_string());
+        }
+        let line = lines.next().unwrap_or_default();
+        let mut tokens = line.split_whitespace();
+        let width: usize = parse_field(tokens.next())?;
+        let height: usize = parse_field(tokens.next())?;
+        let liquid_drag: f32 = parse_field(tokens.next())?;
+        let valid_size = MIN_WORLD_SIZE..=MAX_WORLD_SIZE;
+        if !valid_size.contains(&width) || !valid_size.contains(&height) {
+            return Err(format!("Invalid world size {}x{}", width, height));
+        }
+        if !(0.0..=1.0).contains(&liquid_drag) {
+            return Err(format!("Invalid liquid drag {}", liquid_drag));
+        }
+        let mut world = GameWorld::new(width, height);
+        world.liquid_drag = liquid_drag;
+        for y in 0..height {
+            let row: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+            if row.len() != width {
+                return Err(format!("Expected {} cells in row {}", width, y));
+            }
+            for (x, encoded) in row.into_iter().enumerate() {
+                world.board[x][y] = decode_element(encoded)?;
+            }
+        }
+        for y in 0..height {
+            let row: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+            if row.len() != width {
+                return Err(format!("Expected {} oxygen values in row {}", width, y));
+            }
+            for (x, oxygen) in row.into_iter().enumerate() {
+                world.oxygen[x][y] = parse_field(Some(oxygen))?;
+                if world.oxygen[x][y] > MAX_OXYGEN {
+                    return Err(format!("Invalid oxygen content {}", oxygen));
+                }
+            }
+        }
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            if let Some(field) = line.strip_prefix(FIELD_PREFIX) {
+                world
+                    .gravity_fields
+                    .push(decode_field(field, width, height)?);
+            } else if let Some(body) = line.strip_prefix(BODY_PREFIX) {
+                world
+                    .rigid_bodies
+                    .push(RigidBody::decode(body, width, height)?);
+            } else {
+                return Err(format!("Unexpected line '{}'", line));
+            }
+        }
+        Ok(world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Element;
+    use crate::world::GameWorld;
+    use crate::world::gravity::GravityField;
+
+    /// A saved 16x16 world with a gravity zone and a rigid body
+    fn sample_text() -> String {
+        let mut world = GameWorld::new(16, 16);
+        for x in 2..5 {
+            world.board[x][2] = Element::Wood;
+        }
+        world.create_rigid_bodies(0, 0, 15, 15);
+        world.add_gravity_field(GravityField::Zone {
+            x: 8,
+            y: 8,
+            width: 4,
+            height: 4,
+            gravity: (0.0, -1.0),
+        });
+        world.to_string()
+    }
+    /// The given saved world with the line at the given index replaced
+    fn replace_line(text: &str, index: usize, line: &str) -> String {
+        let mut lines: Vec<&str> = text.lines().collect();
+        lines[index] = line;
+        lines.join("\n")
+    }
+    /// The index of the first line with the given prefix
+    fn line_index(text: &str, prefix: &str) -> usize {
+        text.lines().position(|l| l.starts_with(prefix)).unwrap()
+    }
+
+    #[test]
+    fn test_sample_world_round_trip() {
+        let text = sample_text();
+        let world: GameWorld = text.parse().unwrap();
+        assert_eq!(world.rigid_bodies().len(), 1);
+        assert_eq!(world.to_string(), text);
+    }
+
+    #[test]
+    fn test_bad_header_is_rejected() {
+        let text = replace_line(&sample_text(), 0, "SANDBOX WORLD 2");
+        assert!(text.parse::<GameWorld>().is_err());
+        let text = replace_line(&sample_text(), 1, "16 16");
+        assert!(text.parse::<GameWorld>().is_err());
+        let text = replace_line(&sample_text(), 1, "8 16 0.5");
+        assert!(text.parse::<GameWorld>().is_err());
+    }
+
+    #[test]
+    fn test_rows_of_wrong_length_are_rejected() {
+        let text = sample_text();
+        let short_cells = text.lines().nth(2).unwrap().split_once(',').unwrap().1;
+        assert!(
+            replace_line(&text, 2, short_cells)
+                .parse::<GameWorld>()
+                .is_err()
+        );
+        let long_oxygen = format!("{},0", text.lines().nth(18).unwrap());
+        assert!(
+            replace_line(&text, 18, &long_oxygen)
+                .parse::<GameWorld>()
+                .is_err()
+        );
+        // Missing rows at the end of the file
+        let truncated: Vec<&str> = text.lines().take(10).collect();
+        assert!(truncated.join("\n").parse::<GameWorld>().is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_oxygen_is_rejected() {
+        let text = sample_text();
+        let mut values: Vec<&str> = text.lines().nth(18).unwrap().split(',').collect();
+        values[0] = "100";
+        assert!(
+            replace_line(&text, 18, &values.join(","))
+                .parse::<GameWorld>()
+                .is_ok()
+        );
+        values[0] = "101";
+        assert!(
+            replace_line(&text, 18, &values.join(","))
+                .parse::<GameWorld>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_fields_outside_of_the_world_are_rejected() {
+        let text = sample_text();
+        let index = line_index(&text, "field ");
+        let inside = replace_line(&text, index, "field Zone 12 12 4 4 0 -1");
+        assert!(inside.parse::<GameWorld>().is_ok());
+        let outside = replace_line(&text, index, "field Zone 13 12 4 4 0 -1");
+        assert!(outside.parse::<GameWorld>().is_err());
+        let outside = replace_line(&text, index, "field Attractor 16 0 4 1 Attractor Air");
+        assert!(outside.parse::<GameWorld>().is_err());
+    }
+
+    #[test]
+    fn test_bodies_outside_of_the_world_are_rejected() {
+        let text = sample_text();
+        let index = line_index(&text, "body ");
+        let body = text.lines().nth(index).unwrap();
+        let mut tokens: Vec<&str> = body.split(' ').collect();
+        tokens[1] = "40";
+        assert!(
+            replace_line(&text, index, &tokens.join(" "))
+                .parse::<GameWorld>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unexpected_lines_are_rejected() {
+        let text = format!("{}\nbodies 1 2 3\n", sample_text());
+        assert!(text.parse::<GameWorld>().is_err());
+        // Empty lines after the world are fine
+        let text = format!("{}\n\n", sample_text());
+        assert!(text.parse::<GameWorld>().is_ok());
+    }
+}